@@ -0,0 +1,77 @@
+use crate::{CMOSCenturyHandler, CmosError, PortIO, RTCDateTime, CMOS};
+
+/// Keeps the RTC time captured at boot, to report the uptime
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BootClock {
+	boot: RTCDateTime,
+	last: RTCDateTime,
+}
+
+impl BootClock {
+	/// Reads the RTC and keeps the result as the boot time.
+	/// Fails like [`CMOS::read_rtc`] when there is no RTC.
+	/// # Examples
+	/// ```rust,no_run
+	/// # use cmos::{BootClock, CMOS, CMOSCenturyHandler};
+	/// let mut cmos = unsafe { CMOS::new() };
+	/// let handler = CMOSCenturyHandler::CurrentYear(2019);
+	/// let mut clock = BootClock::capture(&mut cmos, handler).unwrap();
+	/// // Later on
+	/// let now = clock.now(&mut cmos, handler).unwrap();
+	/// let uptime = clock.uptime_secs();
+	/// ```
+	/// [`CMOS::read_rtc`]: struct.CMOS.html#method.read_rtc
+	pub fn capture<P: PortIO>(cmos: &mut CMOS<P>, century_handler: CMOSCenturyHandler) -> Result<BootClock, CmosError> {
+		let boot = cmos.read_rtc(century_handler)?;
+		Ok(BootClock { boot, last: boot })
+	}
+
+	/// Returns the RTC time captured at boot
+	pub fn boot_time(&self) -> RTCDateTime { self.boot }
+
+	/// Reads the RTC, returning the current time, which [`uptime_secs`] then measures from the boot time.
+	/// The last time is kept if the read fails.
+	///
+	/// [`uptime_secs`]: struct.BootClock.html#method.uptime_secs
+	pub fn now<P: PortIO>(
+		&mut self,
+		cmos: &mut CMOS<P>,
+		century_handler: CMOSCenturyHandler,
+	) -> Result<RTCDateTime, CmosError> {
+		self.last = cmos.read_rtc(century_handler)?;
+		Ok(self.last)
+	}
+
+	/// Returns the number of seconds between the boot time and the last call to [`now`].
+	/// Returns `0` if the RTC went backwards since boot.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{BootClock, MockPorts, RTCDateTime, CMOS, CMOSCenturyHandler};
+	/// let mut registers = [0; 128];
+	/// // 2019-01-02T03:04:05, in BCD and 24 hour format
+	/// registers[..10].copy_from_slice(&[0x05, 0, 0x04, 0, 0x03, 0, 0x04, 0x02, 0x01, 0x19]);
+	/// registers[0x0B] = 0x02;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// let handler = CMOSCenturyHandler::CurrentYear(2019);
+	/// let mut clock = BootClock::capture(&mut cmos, handler).unwrap();
+	/// assert_eq!(clock.uptime_secs(), 0);
+	///
+	/// // An hour and 10 seconds later
+	/// cmos.ports_mut().registers[0x00] = 0x15;
+	/// cmos.ports_mut().registers[0x04] = 0x04;
+	/// assert_eq!(clock.now(&mut cmos, handler).unwrap().as_tuple(), (2019, 1, 2, 4, 4, 15));
+	/// assert_eq!(clock.uptime_secs(), 3610);
+	///
+	/// // The RTC was set back to before the boot time
+	/// cmos.ports_mut().registers[0x04] = 0x02;
+	/// clock.now(&mut cmos, handler).unwrap();
+	/// assert_eq!(clock.uptime_secs(), 0);
+	/// assert_eq!(clock.boot_time(), RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 });
+	/// ```
+	/// [`now`]: struct.BootClock.html#method.now
+	pub fn uptime_secs(&self) -> u64 {
+		let uptime = self.last.seconds_since_year_zero() - self.boot.seconds_since_year_zero();
+		uptime.max(0).min(i128::from(u64::MAX)) as u64
+	}
+}
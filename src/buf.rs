@@ -0,0 +1,26 @@
+use core::fmt::{self, Write};
+
+/// Formats into a byte buffer, for the formatting helpers that can't allocate
+pub(crate) struct SliceWriter<'a> {
+	buf: &'a mut [u8],
+	len: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+	pub(crate) fn new(buf: &'a mut [u8]) -> SliceWriter<'a> { SliceWriter { buf, len: 0 } }
+
+	/// Returns the number of bytes written so far
+	pub(crate) fn len(&self) -> usize { self.len }
+}
+
+impl<'a> Write for SliceWriter<'a> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		let end = self.len + s.len();
+		if end > self.buf.len() {
+			return Err(fmt::Error);
+		}
+		self.buf[self.len..end].copy_from_slice(s.as_bytes());
+		self.len = end;
+		Ok(())
+	}
+}
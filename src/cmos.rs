@@ -0,0 +1,1917 @@
+use crate::{
+	bcd_to_binary, CMOSCenturyHandler, CmosError, Confidence, EquipmentInfo, RTCDateTime, RawRtcRegisters, RtcFormat,
+	RtcRegisterMap, ShutdownStatus, TimeScale,
+};
+#[cfg(not(feature = "read-only"))]
+use crate::RtcField;
+use crate::{
+	buf::SliceWriter,
+	port::{HardwarePorts, PortIO, ADDRESS_PORT, DATA_PORT},
+};
+use core::fmt::{self, Write};
+
+/// Number of times the update in progress flag is polled before the RTC is read anyway.
+/// An update takes under 2ms, and a poll roughly 1µs, so the flag only stays set this long on a wedged RTC.
+const UIP_SPIN_LIMIT: u32 = 10_000;
+
+/// Number of times the update in progress flag is polled for the next update before giving up. Updates happen once a
+/// second, so this spans over a second of polls; the flag only stays clear this long on an absent or stopped RTC.
+const UPDATE_SPIN_LIMIT: u32 = 200 * UIP_SPIN_LIMIT;
+
+/// Number of times the time registers are read again for two consecutive reads to agree before the last read is
+/// kept anyway. Values only change once per update, so they keep differing this long on a flaky bus.
+const REREAD_LIMIT: u32 = 100;
+
+/// Value of an alarm field matching any value, the "don't care" code of the RTC. Any value from `0xC0` up works.
+pub const ALARM_DONT_CARE: u8 = 0xC0;
+
+/// The standard CMOS struct
+///
+/// The CMOS is accessed through a [`PortIO`], the machine's ports ([`HardwarePorts`]) unless another one is given
+/// to [`with_ports`].
+///
+/// [`PortIO`]: trait.PortIO.html
+/// [`HardwarePorts`]: struct.HardwarePorts.html
+/// [`with_ports`]: struct.CMOS.html#method.with_ports
+#[derive(Debug)]
+pub struct CMOS<P = HardwarePorts> {
+	ports: P,
+	register_map: RtcRegisterMap,
+	stabilization_polls: u32,
+	detect_day_month_swap: bool,
+	offset_secs: i64,
+	nmi_enabled: bool,
+	index_port: u16,
+	data_port: u16,
+}
+
+/// Implements the CMOS struct
+impl CMOS {
+	/// Create a new CMOS struct
+	///
+	/// # Safety
+	/// The CMOS is accessed through the machine's ports, see [`HardwarePorts::new`] for the caller's obligations.
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// # use cmos::{CMOS, CMOSCenturyHandler};
+	/// let mut cmos = unsafe { CMOS::new() };
+	/// ```
+	/// [`HardwarePorts::new`]: struct.HardwarePorts.html#method.new
+	pub unsafe fn new() -> CMOS { CMOS::with_addresses(ADDRESS_PORT, DATA_PORT) }
+
+	/// Create a new CMOS struct accessing the CMOS through the index port `index` and the data port `data`, instead
+	/// of the standard `0x70` and `0x71`.
+	///
+	/// Only needed on emulated platforms, or chipsets, exposing the CMOS at nonstandard addresses, like a second
+	/// bank of registers behind another pair of ports. Everything else uses [`new`].
+	///
+	/// # Safety
+	/// `index` and `data` must be the index and data ports of a CMOS, and nothing else may access them at the same
+	/// time: an access interleaved with another one reads or writes the wrong register. The caller must also be
+	/// allowed to do port I/O, like for [`new`].
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// # use cmos::CMOS;
+	/// let mut cmos = unsafe { CMOS::with_addresses(0x72, 0x73) };
+	/// ```
+	/// [`new`]: struct.CMOS.html#method.new
+	pub unsafe fn with_addresses(index: u16, data: u16) -> CMOS { CMOS::with_ports_at(HardwarePorts::new(), index, data) }
+}
+
+impl<P: PortIO> CMOS<P> {
+	/// Create a new CMOS struct accessing the CMOS through `ports`
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{MockPorts, CMOS};
+	/// let mut cmos = CMOS::with_ports(MockPorts::new([0; 128]));
+	/// ```
+	pub fn with_ports(ports: P) -> CMOS<P> { CMOS::with_ports_at(ports, ADDRESS_PORT, DATA_PORT) }
+
+	/// Create a new CMOS struct accessing the CMOS through `ports`, using the index port `index` and the data port
+	/// `data`, like [`with_addresses`] does for the machine's ports
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{MockPorts, PortIO, CMOS};
+	/// // Exposes the mock CMOS at 0x72 and 0x73
+	/// struct Moved(MockPorts);
+	///
+	/// impl PortIO for Moved {
+	///     fn read(&mut self, port: u16) -> u8 { if port == 0x73 { self.0.read(0x71) } else { 0xFF } }
+	///     fn write(&mut self, port: u16, val: u8) {
+	///         match port {
+	///             0x72 => self.0.write(0x70, val),
+	///             0x73 => self.0.write(0x71, val),
+	///             _ => {},
+	///         }
+	///     }
+	/// }
+	///
+	/// let mut registers = [0; 128];
+	/// registers[0x04] = 0x08;
+	/// let mut cmos = CMOS::with_ports_at(Moved(MockPorts::new(registers)), 0x72, 0x73);
+	/// assert_eq!(cmos.read(0x04), 0x08);
+	/// // The standard ports aren't used
+	/// assert_eq!(CMOS::with_ports(Moved(MockPorts::new(registers))).read(0x04), 0xFF);
+	/// ```
+	/// [`with_addresses`]: struct.CMOS.html#method.with_addresses
+	pub fn with_ports_at(ports: P, index: u16, data: u16) -> CMOS<P> {
+		CMOS {
+			ports,
+			register_map: RtcRegisterMap::standard(),
+			stabilization_polls: 0,
+			detect_day_month_swap: false,
+			offset_secs: 0,
+			nmi_enabled: true,
+			index_port: index,
+			data_port: data,
+		}
+	}
+
+	/// Returns the ports the CMOS is accessed through
+	pub fn ports(&self) -> &P { &self.ports }
+
+	/// Returns the ports the CMOS is accessed through, mutably
+	pub fn ports_mut(&mut self) -> &mut P { &mut self.ports }
+
+	/// Enables or disables non-maskable interrupts (NMIs), through bit 7 of the CMOS address port (`0x70` by default).
+	///
+	/// The address port holds both the selected register, in the low 7 bits, and the NMI disable bit, so every
+	/// register access also sets the NMI state. Writing the bare register number, as a naive driver does, silently
+	/// enables NMIs again after the OS disabled them. Every access made by this struct keeps the state set here
+	/// instead: NMIs stay enabled by default, which is the state the firmware hands over, and stay disabled across
+	/// all accesses once disabled with this method. The register number is masked to 7 bits, so it can't change the
+	/// NMI state by itself.
+	///
+	/// The new state is written out straight away, by selecting register D.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{PortIO, CMOS};
+	/// // Records the last value written to the address port
+	/// struct Recorder(u8);
+	///
+	/// impl PortIO for Recorder {
+	///     fn read(&mut self, _port: u16) -> u8 { 0 }
+	///     fn write(&mut self, port: u16, val: u8) {
+	///         if port == 0x70 {
+	///             self.0 = val;
+	///         }
+	///     }
+	/// }
+	///
+	/// let mut cmos = CMOS::with_ports(Recorder(0));
+	/// cmos.read(0x0A);
+	/// assert_eq!(cmos.ports().0, 0x0A);
+	/// cmos.set_nmi_enabled(false);
+	/// assert_eq!(cmos.ports().0, 0x8D);
+	/// cmos.read(0x0A);
+	/// assert_eq!(cmos.ports().0, 0x8A);
+	/// cmos.set_nmi_enabled(true);
+	/// cmos.read(0x8A);
+	/// assert_eq!(cmos.ports().0, 0x0A);
+	/// ```
+	pub fn set_nmi_enabled(&mut self, enabled: bool) {
+		self.nmi_enabled = enabled;
+		self.select(0x0D);
+	}
+
+	/// Selects a register through the address port, keeping the NMI state
+	fn select(&mut self, reg: u8) {
+		let nmi_disable = if self.nmi_enabled { 0 } else { 0x80 };
+		self.ports.write(self.index_port, (reg & 0x7F) | nmi_disable);
+	}
+
+	/// Sets the register layout used when reading the RTC
+	///
+	/// Only needed for RTCs (or emulators) that don't use the standard register offsets.
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{CMOS, CMOSCenturyHandler, MockPorts, RtcRegisterMap};
+	/// let mut registers = [0; 128];
+	/// registers[..10].copy_from_slice(&[0x05, 0, 0x04, 0, 0x03, 0, 0x04, 0x02, 0x01, 0x19]);
+	/// registers[0x0B] = 0x02;
+	/// // Year is stored in register 0x40 instead of 0x09
+	/// registers[0x40] = 0x21;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// cmos.set_register_map(RtcRegisterMap { year: 0x40, ..RtcRegisterMap::standard() });
+	/// let rtc = cmos.read_rtc(CMOSCenturyHandler::CurrentYear(2019)).unwrap();
+	/// assert_eq!(rtc.as_tuple(), (2021, 1, 2, 3, 4, 5));
+	/// ```
+	pub fn set_register_map(&mut self, register_map: RtcRegisterMap) { self.register_map = register_map; }
+
+	/// Enables detecting RTCs that swap the day and month registers.
+	///
+	/// Some cloned chipsets store the day at `0x08` and the month at `0x07`. When the layout is known,
+	/// [`RtcRegisterMap::swapped_day_month`] reads it directly. Otherwise, with detection enabled, a read whose
+	/// month is out of range (above 12) while its day would be a valid month is read the other way around. A date
+	/// where both are 12 or below is ambiguous and is kept as read, so detection only catches the swap from the
+	/// 13th of the month on. Disabled by default.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{CMOS, CMOSCenturyHandler, MockPorts, RtcRegisterMap};
+	/// let mut registers = [0; 128];
+	/// // 2019-01-25T03:04:05, with the day at 0x08 and the month at 0x07
+	/// registers[..10].copy_from_slice(&[0x05, 0, 0x04, 0, 0x03, 0, 0x06, 0x01, 0x25, 0x19]);
+	/// registers[0x0B] = 0x02;
+	/// let handler = CMOSCenturyHandler::CurrentYear(2019);
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// assert_eq!(cmos.read_rtc(handler).unwrap().as_tuple(), (2019, 25, 1, 3, 4, 5));
+	///
+	/// // A known layout
+	/// cmos.set_register_map(RtcRegisterMap::swapped_day_month());
+	/// assert_eq!(cmos.read_rtc(handler).unwrap().as_tuple(), (2019, 1, 25, 3, 4, 5));
+	///
+	/// // Detected from the out of range month
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// cmos.set_detect_day_month_swap(true);
+	/// assert_eq!(cmos.read_rtc(handler).unwrap().as_tuple(), (2019, 1, 25, 3, 4, 5));
+	/// // The 12th of January is ambiguous, and kept as read
+	/// cmos.ports_mut().registers[0x08] = 0x12;
+	/// assert_eq!(cmos.read_rtc(handler).unwrap().as_tuple(), (2019, 12, 1, 3, 4, 5));
+	/// // The standard layout is read as is
+	/// cmos.ports_mut().registers[0x07] = 0x25;
+	/// cmos.ports_mut().registers[0x08] = 0x01;
+	/// assert_eq!(cmos.read_rtc(handler).unwrap().as_tuple(), (2019, 1, 25, 3, 4, 5));
+	/// ```
+	/// [`RtcRegisterMap::swapped_day_month`]: struct.RtcRegisterMap.html#method.swapped_day_month
+	pub fn set_detect_day_month_swap(&mut self, detect: bool) { self.detect_day_month_swap = detect; }
+
+	/// Sets how many times register A is polled between the two consecutive reads [`read_rtc`] compares.
+	///
+	/// The update in progress flag is set up to 244µs before an update, and the update itself takes under 2ms.
+	/// A fast poll can miss the flag entirely, and two reads taken in quick succession can then both land inside
+	/// that window and agree on values that are being updated. Spacing the reads out so they span the window means
+	/// the values, the seconds in particular, have to stay stable across a whole update to be accepted.
+	/// Each poll is a port I/O access, roughly 1µs on most hardware, so around 2000 polls span the window.
+	/// Defaults to 0.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{CMOS, CMOSCenturyHandler, MockPorts, PortIO};
+	/// // Ticks over from 03:04:59 to 03:05:00 without ever reporting the update in progress, with the seconds
+	/// // updated from the 2nd poll of register A and the minutes only from the 10th
+	/// struct Flipping {
+	///     ports: MockPorts,
+	///     address: u8,
+	///     polls: u32,
+	/// }
+	///
+	/// impl PortIO for Flipping {
+	///     fn read(&mut self, port: u16) -> u8 {
+	///         if self.address == 0x0A {
+	///             self.polls += 1;
+	///             match self.polls {
+	///                 2 => self.ports.registers[0x00] = 0x00,
+	///                 10 => self.ports.registers[0x02] = 0x05,
+	///                 _ => {},
+	///             }
+	///         }
+	///         self.ports.read(port)
+	///     }
+	///
+	///     fn write(&mut self, port: u16, val: u8) {
+	///         if port == 0x70 {
+	///             self.address = val;
+	///         }
+	///         self.ports.write(port, val);
+	///     }
+	/// }
+	///
+	/// let mut registers = [0; 128];
+	/// registers[..10].copy_from_slice(&[0x59, 0, 0x04, 0, 0x03, 0, 0x04, 0x02, 0x01, 0x19]);
+	/// registers[0x0B] = 0x02;
+	/// let flipping = || Flipping { ports: MockPorts::new(registers), address: 0, polls: 0 };
+	/// let handler = CMOSCenturyHandler::CurrentYear(2019);
+	///
+	/// // Two reads in quick succession agree on the half updated 03:04:00
+	/// let mut cmos = CMOS::with_ports(flipping());
+	/// assert_eq!(cmos.read_rtc(handler).unwrap().as_tuple(), (2019, 1, 2, 3, 4, 0));
+	///
+	/// // Reads spanning the update only agree once it is over
+	/// let mut cmos = CMOS::with_ports(flipping());
+	/// cmos.set_stabilization_polls(20);
+	/// assert_eq!(cmos.read_rtc(handler).unwrap().as_tuple(), (2019, 1, 2, 3, 5, 0));
+	/// ```
+	/// [`read_rtc`]: struct.CMOS.html#method.read_rtc
+	pub fn set_stabilization_polls(&mut self, polls: u32) { self.stabilization_polls = polls; }
+
+	/// Returns the CMOS with a calibration offset, in seconds, added to every date time read from the RTC.
+	///
+	/// This corrects a known error in software, for example as measured against NTP, without rewriting the hardware
+	/// clock: if the RTC runs 3 seconds fast, an offset of `-3` gives the right time. The correction carries into the
+	/// minutes, hours, days, months and years, and saturates at [`MIN`] and [`MAX`]. It isn't applied to invalid
+	/// reads, which are returned as is, nor to the date times written with [`write_rtc`].
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// # use cmos::{CMOS, CMOSCenturyHandler};
+	/// // The RTC was measured 3 seconds fast
+	/// let mut cmos = unsafe { CMOS::new() }.with_offset(-3);
+	/// let rtc = cmos.read_rtc(CMOSCenturyHandler::CurrentYear(2019));
+	/// ```
+	/// The correction rolls over into the next or previous day:
+	/// ```rust
+	/// # use cmos::{CMOS, CMOSCenturyHandler, MockPorts};
+	/// let handler = CMOSCenturyHandler::CurrentYear(2019);
+	/// let mut registers = [0; 128];
+	/// registers[0x0B] = 0x02;
+	///
+	/// // 2019-12-31T23:59:50, 20 seconds slow
+	/// registers[..10].copy_from_slice(&[0x50, 0, 0x59, 0, 0x23, 0, 0x03, 0x31, 0x12, 0x19]);
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers)).with_offset(20);
+	/// assert_eq!(cmos.read_rtc(handler).unwrap().as_tuple(), (2020, 1, 1, 0, 0, 10));
+	///
+	/// // 2019-03-01T00:00:05, 10 seconds fast
+	/// registers[..10].copy_from_slice(&[0x05, 0, 0x00, 0, 0x00, 0, 0x06, 0x01, 0x03, 0x19]);
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers)).with_offset(-10);
+	/// assert_eq!(cmos.read_rtc(handler).unwrap().as_tuple(), (2019, 2, 28, 23, 59, 55));
+	/// ```
+	/// [`MIN`]: constant.MIN.html
+	/// [`MAX`]: constant.MAX.html
+	/// [`write_rtc`]: struct.CMOS.html#method.write_rtc
+	pub fn with_offset(mut self, offset_secs: i64) -> CMOS<P> {
+		self.offset_secs = offset_secs;
+		self
+	}
+
+	/// Converts the raw time registers with [`RawRtcRegisters::convert`], then undoes a day and month swap if
+	/// detection is enabled and applies the calibration offset
+	fn convert_rtc(&self, raw: &RawRtcRegisters, century_handler: CMOSCenturyHandler) -> RTCDateTime {
+		let mut converted = raw.convert(century_handler);
+		if self.detect_day_month_swap && converted.month > 12 && (1..=12).contains(&converted.day) {
+			converted = RTCDateTime { day: converted.month, month: converted.day, ..converted };
+		}
+		if self.offset_secs != 0 && converted.invalid_field().is_none() {
+			let seconds = converted.seconds_since_year_zero() + i128::from(self.offset_secs);
+			converted = RTCDateTime::from_seconds_since_year_zero(seconds);
+		}
+		converted
+	}
+
+	/// Reads all the registers in CMOS
+	/// # Examples
+	/// ```rust,no_run
+	/// # use cmos::{CMOS, CMOSCenturyHandler};
+	/// let mut cmos = unsafe { CMOS::new() };
+	/// // Blank array to read into
+	/// let mut cmos_values: [u8; 128] = [0; 128];
+	/// // Read values into provided array
+	/// cmos.read_all(&mut cmos_values);
+	/// ```
+	pub fn read_all(&mut self, output: &mut [u8; 128]) {
+		for i in 0..128 {
+			output[i as usize] = self.read(i);
+		}
+	}
+
+	/// Writes to all the registers in CMOS
+	/// # Examples
+	/// Writes all 0's, probably not a best idea to actually do this
+	/// ```rust,no_run
+	/// # use cmos::{CMOS, CMOSCenturyHandler};
+	/// let mut cmos = unsafe { CMOS::new() };
+	/// // Example values to write (don't do this!)
+	/// let values: [u8; 128] = [0; 128];
+	/// // Writes values to all CMOS registers
+	/// cmos.write_all(&values);
+	/// ```
+	#[cfg(not(feature = "read-only"))]
+	pub fn write_all(&mut self, input: &[u8; 128]) {
+		for i in 0..128 {
+			self.write(i, input[i as usize]);
+		}
+	}
+
+	/// Reads from a singe register in CMOS
+	/// # Examples
+	/// ```rust,no_run
+	/// # use cmos::{CMOS, CMOSCenturyHandler};
+	/// let mut cmos = unsafe { CMOS::new() };
+	/// // Read from register 0x04 in the CMOS
+	/// let reg_4 = cmos.read(0x04);
+	/// ```
+	pub fn read(&mut self, reg: u8) -> u8 {
+		self.select(reg);
+		self.ports.read(self.data_port)
+	}
+
+	/// Writes to a singe register in CMOS
+	/// # Examples
+	/// Writes `0x08` to register `0x04`
+	/// ```rust,no_run
+	/// # use cmos::{CMOS, CMOSCenturyHandler};
+	/// let mut cmos = unsafe { CMOS::new() };
+	/// // Write 0x08 into register 0x04
+	/// cmos.write(0x04, 0x08);
+	/// ```
+	#[cfg(not(feature = "read-only"))]
+	pub fn write(&mut self, reg: u8, val: u8) {
+		self.select(reg);
+		self.ports.write(self.data_port, val);
+	}
+
+	/// Reads and checks the status of the update in progress flag.
+	/// When reading from the RTC, it's best to read until this flag is 0.
+	///
+	/// More info found [here](https://wiki.osdev.org/CMOS#RTC_Update_In_Progress)
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// # use cmos::{CMOS, CMOSCenturyHandler};
+	/// let mut cmos = unsafe { CMOS::new() };
+	/// let mut reg0;
+	/// // Read register 0x00 until progress flag not 0
+	/// while cmos.get_update_in_progress_flag() != 0 {
+	///     reg0 = cmos.read(0x00);
+	/// }
+	/// ```
+	/// [`CMOS`]: struct.CMOS.html
+	pub fn get_update_in_progress_flag(&mut self) -> u8 { self.read(0x0A) & 0x80 }
+
+	/// Reads the data format of the RTC time registers from register B
+	/// # Examples
+	/// ```rust,no_run
+	/// # use cmos::CMOS;
+	/// let mut cmos = unsafe { CMOS::new() };
+	/// let format = cmos.read_format();
+	/// ```
+	pub fn read_format(&mut self) -> RtcFormat { RtcFormat::from_register_b(self.read(0x0B)) }
+
+	/// Reads only the seconds register, which is cheap enough to poll for the second ticking over.
+	/// The register holds BCD or binary depending on the RTC format, so `format` (usually read once with
+	/// [`read_format`] and cached) tells whether BCD has to be converted. The returned value is always binary.
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// # use cmos::CMOS;
+	/// let mut cmos = unsafe { CMOS::new() };
+	/// let format = cmos.read_format();
+	/// let second = cmos.read_seconds(format);
+	/// // Wait for the next second
+	/// while cmos.read_seconds(format) == second {}
+	/// ```
+	/// [`read_format`]: struct.CMOS.html#method.read_format
+	pub fn read_seconds(&mut self, format: RtcFormat) -> u8 {
+		let second = self.read(self.register_map.second);
+		if format.binary {
+			second
+		} else {
+			bcd_to_binary(second)
+		}
+	}
+
+	/// Reads the weekday register (`0x06` in the standard layout), from 1 for Sunday to 7 for Saturday.
+	/// The value is converted from BCD if register B says so, like the other time registers.
+	///
+	/// Note: Many BIOSes don't maintain this register, so it can be stale or 0. [`RTCDateTime::weekday`] computes
+	/// the weekday from the date instead.
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// # use cmos::CMOS;
+	/// let mut cmos = unsafe { CMOS::new() };
+	/// let weekday = cmos.read_weekday();
+	/// ```
+	/// [`RTCDateTime::weekday`]: struct.RTCDateTime.html#method.weekday
+	pub fn read_weekday(&mut self) -> u8 {
+		let format = self.read_format();
+		let weekday = self.read(self.register_map.weekday);
+		if format.binary {
+			weekday
+		} else {
+			bcd_to_binary(weekday)
+		}
+	}
+
+	/// Reads the legacy equipment byte (register `0x14`), set by the BIOS setup. See [`read_equipment_info`] to
+	/// decode it.
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{MockPorts, CMOS};
+	/// let mut registers = [0; 128];
+	/// registers[0x14] = 0x2F;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// assert_eq!(cmos.read_equipment(), 0x2F);
+	/// ```
+	/// [`read_equipment_info`]: struct.CMOS.html#method.read_equipment_info
+	pub fn read_equipment(&mut self) -> u8 { self.read(0x14) }
+
+	/// Reads and decodes the legacy equipment byte (register `0x14`), to probe legacy hardware.
+	/// Modern firmware often leaves it stale or zeroed, so it is only a hint.
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{DisplayType, MockPorts, CMOS};
+	/// let mut registers = [0; 128];
+	/// registers[0x14] = 0x2F;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// let info = cmos.read_equipment_info();
+	/// assert_eq!((info.floppy_drives, info.display, info.math_coprocessor), (1, DisplayType::Cga80, true));
+	/// ```
+	pub fn read_equipment_info(&mut self) -> EquipmentInfo { EquipmentInfo::from_byte(self.read_equipment()) }
+
+	/// Reads the shutdown status code (register `0x0F`), telling the BIOS what to do after a CPU reset
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{MockPorts, ShutdownStatus, CMOS};
+	/// let mut registers = [0; 128];
+	/// registers[0x0F] = 0x0A;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// assert_eq!(cmos.read_shutdown_status(), ShutdownStatus::JumpWithoutEoi);
+	/// ```
+	pub fn read_shutdown_status(&mut self) -> ShutdownStatus { ShutdownStatus::from(self.read(0x0F)) }
+
+	/// Writes the shutdown status code (register `0x0F`), before resetting the CPU. See [`ShutdownStatus`] for the
+	/// reset vector to set along with it.
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{MockPorts, ShutdownStatus, CMOS};
+	/// let mut cmos = CMOS::with_ports(MockPorts::new([0; 128]));
+	/// cmos.write_shutdown_status(ShutdownStatus::JumpWithEoi);
+	/// assert_eq!(cmos.ports().registers[0x0F], 0x05);
+	/// assert_eq!(cmos.read_shutdown_status(), ShutdownStatus::JumpWithEoi);
+	/// ```
+	/// [`ShutdownStatus`]: enum.ShutdownStatus.html
+	#[cfg(not(feature = "read-only"))]
+	pub fn write_shutdown_status(&mut self, status: ShutdownStatus) { self.write(0x0F, u8::from(status)) }
+
+	/// Reads the standard CMOS checksum, stored big endian in registers `0x2E` (high byte) and `0x2F` (low byte)
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{MockPorts, CMOS};
+	/// let mut registers = [0; 128];
+	/// registers[0x2E] = 0x12;
+	/// registers[0x2F] = 0x34;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// assert_eq!(cmos.read_checksum(), 0x1234);
+	/// ```
+	pub fn read_checksum(&mut self) -> u16 { u16::from_be_bytes([self.read(0x2E), self.read(0x2F)]) }
+
+	/// Computes the standard CMOS checksum: the sum of registers `0x10` to `0x2D`, the BIOS settings it covers.
+	/// It matches [`read_checksum`] unless the settings were corrupted, or changed without updating the checksum.
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{MockPorts, CMOS};
+	/// let mut registers = [0; 128];
+	/// for reg in 0x10..=0x2D {
+	///     registers[reg] = reg as u8;
+	/// }
+	/// // Outside of the checksummed range
+	/// registers[0x0F] = 0xFF;
+	/// registers[0x2E] = 0xFF;
+	/// registers[0x30] = 0xFF;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// assert_eq!(cmos.compute_checksum(), (0x10..=0x2D).sum());
+	/// assert_eq!(cmos.compute_checksum(), 0x0393);
+	///
+	/// // The sum of 30 bytes fits in 16 bits, without wrapping
+	/// let mut cmos = CMOS::with_ports(MockPorts::new([0xFF; 128]));
+	/// assert_eq!(cmos.compute_checksum(), 30 * 0xFF);
+	/// ```
+	/// [`read_checksum`]: struct.CMOS.html#method.read_checksum
+	pub fn compute_checksum(&mut self) -> u16 { (0x10..=0x2D).map(|reg| u16::from(self.read(reg))).sum() }
+
+	/// Returns whether the stored checksum ([`read_checksum`]) matches the settings ([`compute_checksum`]).
+	/// A mismatch means the BIOS settings are corrupted, and the firmware will usually reset them on the next boot.
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{MockPorts, CMOS};
+	/// let mut registers = [0; 128];
+	/// registers[0x10] = 0x40;
+	/// registers[0x2D] = 0x02;
+	/// registers[0x2F] = 0x42;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// assert!(cmos.verify_checksum());
+	/// cmos.ports_mut().registers[0x20] = 0x01;
+	/// assert!(!cmos.verify_checksum());
+	/// ```
+	/// [`read_checksum`]: struct.CMOS.html#method.read_checksum
+	/// [`compute_checksum`]: struct.CMOS.html#method.compute_checksum
+	pub fn verify_checksum(&mut self) -> bool { self.read_checksum() == self.compute_checksum() }
+
+	/// Recomputes the checksum and stores it, like the BIOS does after changing its settings. Needed after writing
+	/// any register from `0x10` to `0x2D`, to keep the firmware from resetting the settings on the next boot.
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{MockPorts, CMOS};
+	/// let mut cmos = CMOS::with_ports(MockPorts::new([0; 128]));
+	/// cmos.write(0x14, 0x2F);
+	/// assert!(!cmos.verify_checksum());
+	/// cmos.update_checksum();
+	/// assert!(cmos.verify_checksum());
+	/// assert_eq!(cmos.read_checksum(), 0x2F);
+	/// ```
+	#[cfg(not(feature = "read-only"))]
+	pub fn update_checksum(&mut self) {
+		let sum = self.compute_checksum();
+		self.write_checksum(sum);
+	}
+
+	/// Writes the standard CMOS checksum, big endian in registers `0x2E` (high byte) and `0x2F` (low byte)
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{MockPorts, CMOS};
+	/// let mut cmos = CMOS::with_ports(MockPorts::new([0; 128]));
+	/// cmos.write_checksum(0x1234);
+	/// assert_eq!(&cmos.ports().registers[0x2E..0x30], &[0x12, 0x34]);
+	/// assert_eq!(cmos.read_checksum(), 0x1234);
+	/// ```
+	#[cfg(not(feature = "read-only"))]
+	pub fn write_checksum(&mut self, sum: u16) {
+		let [high, low] = sum.to_be_bytes();
+		self.write(0x2E, high);
+		self.write(0x2F, low);
+	}
+
+	/// Reads the alarm registers (`0x05`, `0x03` and `0x01`), returning the alarm hour, minute and second.
+	/// The values are converted from BCD and from 12 hour format if register B says so, like [`read_rtc`] does.
+	/// A field at [`ALARM_DONT_CARE`] or above matches any value and is returned as is.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{MockPorts, ALARM_DONT_CARE, CMOS};
+	/// let mut registers = [0; 128];
+	/// // 15:30, any second, in BCD and 24 hour format
+	/// registers[0x01] = ALARM_DONT_CARE;
+	/// registers[0x03] = 0x30;
+	/// registers[0x05] = 0x15;
+	/// registers[0x0B] = 0x02;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// assert_eq!(cmos.read_alarm(), (15, 30, ALARM_DONT_CARE));
+	/// ```
+	/// [`read_rtc`]: struct.CMOS.html#method.read_rtc
+	/// [`ALARM_DONT_CARE`]: constant.ALARM_DONT_CARE.html
+	pub fn read_alarm(&mut self) -> (u8, u8, u8) {
+		let format = self.read_format();
+		let decode = |value: u8| {
+			if value >= ALARM_DONT_CARE || format.binary {
+				value
+			} else {
+				bcd_to_binary(value)
+			}
+		};
+		let hour = self.read(0x05);
+		let hour = if hour >= ALARM_DONT_CARE || format.hour_24 {
+			decode(hour)
+		} else {
+			// 12 hour clock: 12 AM is midnight, 12 PM is noon, and bit 7 is set for PM
+			let pm = if hour & 0x80 != 0 { 12 } else { 0 };
+			decode(hour & 0x7F) % 12 + pm
+		};
+		(hour, decode(self.read(0x03)), decode(self.read(0x01)))
+	}
+
+	/// Writes the alarm registers (`0x05`, `0x03` and `0x01`) with the alarm hour, minute and second, in the format
+	/// currently configured in register B. A field at [`ALARM_DONT_CARE`] or above is written as is, making the
+	/// alarm match any value of that field.
+	///
+	/// Note: This only sets the alarm time. The alarm interrupt has to be enabled separately, with bit 5 of
+	/// register B (see [`enable_alarm_interrupt`] or [`RtcConfigBuilder::alarm_interrupt`]), for the RTC to raise
+	/// IRQ8 when the time matches.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{MockPorts, ALARM_DONT_CARE, CMOS};
+	/// // BCD and 12 hour format
+	/// let mut cmos = CMOS::with_ports(MockPorts::new([0; 128]));
+	/// cmos.write_alarm(15, 30, ALARM_DONT_CARE);
+	/// assert_eq!(cmos.ports().registers[0x05], 0x83);
+	/// assert_eq!(cmos.ports().registers[0x03], 0x30);
+	/// assert_eq!(cmos.ports().registers[0x01], ALARM_DONT_CARE);
+	/// assert_eq!(cmos.read_alarm(), (15, 30, ALARM_DONT_CARE));
+	///
+	/// // Binary and 24 hour format
+	/// cmos.write(0x0B, 0x06);
+	/// cmos.write_alarm(0, 5, 59);
+	/// assert_eq!(cmos.ports().registers[0x05], 0);
+	/// assert_eq!(cmos.ports().registers[0x01], 59);
+	/// assert_eq!(cmos.read_alarm(), (0, 5, 59));
+	/// ```
+	/// [`ALARM_DONT_CARE`]: constant.ALARM_DONT_CARE.html
+	/// [`enable_alarm_interrupt`]: struct.CMOS.html#method.enable_alarm_interrupt
+	/// [`RtcConfigBuilder::alarm_interrupt`]: struct.RtcConfigBuilder.html#method.alarm_interrupt
+	#[cfg(not(feature = "read-only"))]
+	pub fn write_alarm(&mut self, h: u8, m: u8, s: u8) {
+		let format = self.read_format();
+		let encode = |value: u8| {
+			if value >= ALARM_DONT_CARE || format.binary {
+				value
+			} else {
+				((value / 10) << 4) | (value % 10)
+			}
+		};
+		let hour = if h >= ALARM_DONT_CARE || format.hour_24 {
+			encode(h)
+		} else {
+			let pm = if h >= 12 { 0x80 } else { 0 };
+			encode(match h % 12 {
+				0 => 12,
+				hour => hour,
+			}) | pm
+		};
+		self.write(0x05, hour);
+		self.write(0x03, encode(m));
+		self.write(0x01, encode(s));
+	}
+
+	/// Returns whether the current RTC time matches the alarm, for polling the alarm without its interrupt.
+	/// Alarm fields at [`ALARM_DONT_CARE`] or above match any value. The RTC time is compared as the hardware keeps
+	/// it, ignoring the offset set with [`with_offset`].
+	///
+	/// Note: With the seconds set, the time only matches for one second: poll at least once per second.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{MockPorts, ALARM_DONT_CARE, CMOS};
+	/// let mut registers = [0; 128];
+	/// // 03:04:05, in BCD and 24 hour format
+	/// registers[..10].copy_from_slice(&[0x05, 0, 0x04, 0, 0x03, 0, 0x04, 0x02, 0x01, 0x19]);
+	/// registers[0x0B] = 0x02;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// let mut set_alarm = |cmos: &mut CMOS<MockPorts>, hour, minute, second| {
+	///     let registers = &mut cmos.ports_mut().registers;
+	///     registers[0x05] = hour;
+	///     registers[0x03] = minute;
+	///     registers[0x01] = second;
+	/// };
+	/// set_alarm(&mut cmos, 0x03, 0x04, 0x05);
+	/// assert!(cmos.alarm_matches_now());
+	/// set_alarm(&mut cmos, 0x03, 0x04, 0x06);
+	/// assert!(!cmos.alarm_matches_now());
+	/// set_alarm(&mut cmos, ALARM_DONT_CARE, 0x04, ALARM_DONT_CARE);
+	/// assert!(cmos.alarm_matches_now());
+	/// set_alarm(&mut cmos, ALARM_DONT_CARE, 0x05, ALARM_DONT_CARE);
+	/// assert!(!cmos.alarm_matches_now());
+	/// ```
+	/// [`ALARM_DONT_CARE`]: constant.ALARM_DONT_CARE.html
+	/// [`with_offset`]: struct.CMOS.html#method.with_offset
+	pub fn alarm_matches_now(&mut self) -> bool {
+		let (hour, minute, second) = self.read_alarm();
+		// Only the time is compared, so the year (and the century handler) doesn't matter
+		let handler = CMOSCenturyHandler::CurrentYear(2000);
+		let now = self.read_rtc_raw(handler, &mut || {}).convert(handler);
+		let matches = |alarm: u8, value: u8| alarm >= ALARM_DONT_CARE || alarm == value;
+		matches(hour, now.hour) && matches(minute, now.minute) && matches(second, now.second)
+	}
+
+	/// Sets the periodic interrupt rate in the low 4 bits of register A, keeping the update in progress and divider
+	/// bits. The interrupt fires at [`periodic_rate_to_hz`] of the rate, once enabled with
+	/// [`enable_periodic_interrupt`].
+	///
+	/// `rate` should be 0, to stop the interrupt, or between 3 (8192Hz) and 15 (2Hz). Other rates return
+	/// [`CmosError::InvalidPeriodicRate`] without writing anything, like [`RtcConfigBuilder::apply`].
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{CmosError, MockPorts, CMOS};
+	/// let mut registers = [0; 128];
+	/// registers[0x0A] = 0x26;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// cmos.set_periodic_rate(15).unwrap();
+	/// assert_eq!(cmos.read(0x0A), 0x2F);
+	/// cmos.set_periodic_rate(0).unwrap();
+	/// assert_eq!(cmos.read(0x0A), 0x20);
+	///
+	/// assert_eq!(cmos.set_periodic_rate(2), Err(CmosError::InvalidPeriodicRate(2)));
+	/// assert_eq!(cmos.set_periodic_rate(16), Err(CmosError::InvalidPeriodicRate(16)));
+	/// assert_eq!(cmos.read(0x0A), 0x20);
+	/// ```
+	/// [`periodic_rate_to_hz`]: fn.periodic_rate_to_hz.html
+	/// [`enable_periodic_interrupt`]: struct.CMOS.html#method.enable_periodic_interrupt
+	/// [`CmosError::InvalidPeriodicRate`]: enum.CmosError.html#variant.InvalidPeriodicRate
+	/// [`RtcConfigBuilder::apply`]: struct.RtcConfigBuilder.html#method.apply
+	#[cfg(not(feature = "read-only"))]
+	pub fn set_periodic_rate(&mut self, rate: u8) -> Result<(), CmosError> {
+		match rate {
+			0 | 3..=15 => {},
+			rate => return Err(CmosError::InvalidPeriodicRate(rate)),
+		}
+		let register_a = self.read(0x0A);
+		self.write(0x0A, (register_a & 0xF0) | rate);
+		Ok(())
+	}
+
+	/// Acknowledges an RTC interrupt (IRQ8) and returns whether it was a periodic interrupt.
+	/// This reads register C, which is required for the RTC to raise further interrupts.
+	///
+	/// Note: Reading register C clears all of its pending flags (periodic, alarm and update ended), not just the
+	/// periodic one.
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// # use cmos::CMOS;
+	/// let mut cmos = unsafe { CMOS::new() };
+	/// // In the IRQ8 handler
+	/// if cmos.ack_periodic() {
+	///     // Periodic tick
+	/// }
+	/// ```
+	pub fn ack_periodic(&mut self) -> bool { self.read(0x0C) & 0x40 != 0 }
+
+	/// Enables the update ended interrupt (bit 4 of register B), raised once per second right after the RTC updates.
+	///
+	/// The RTC interrupt is IRQ8, on the slave PIC. Its handler has to read register C, for example with
+	/// [`on_update_ended`], or the RTC won't raise any further interrupt.
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// # use cmos::CMOS;
+	/// let mut cmos = unsafe { CMOS::new() };
+	/// cmos.enable_update_interrupt();
+	/// // In the IRQ8 handler
+	/// if cmos.on_update_ended() {
+	///     // The RTC just ticked, refresh the clock display
+	/// }
+	/// ```
+	/// [`on_update_ended`]: struct.CMOS.html#method.on_update_ended
+	#[cfg(not(feature = "read-only"))]
+	pub fn enable_update_interrupt(&mut self) { self.set_register_b_bits(0x10, true); }
+
+	/// Disables the update ended interrupt (bit 4 of register B)
+	#[cfg(not(feature = "read-only"))]
+	pub fn disable_update_interrupt(&mut self) { self.set_register_b_bits(0x10, false); }
+
+	/// Enables the periodic interrupt (bit 6 of register B), raised at the rate selected in register A.
+	///
+	/// Note: Like for the other RTC interrupts, the caller is responsible for the interrupt handling around it: the
+	/// IRQ8 handler has to read register C, for example with [`ack_periodic`], to clear the pending interrupt, and
+	/// NMIs aren't disabled while register B is updated.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{MockPorts, CMOS};
+	/// let mut registers = [0; 128];
+	/// registers[0x0B] = 0x02;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// cmos.enable_periodic_interrupt();
+	/// cmos.enable_alarm_interrupt();
+	/// assert_eq!(cmos.read(0x0B), 0x62);
+	/// cmos.disable_periodic_interrupt();
+	/// assert_eq!(cmos.read(0x0B), 0x22);
+	/// cmos.enable_update_interrupt();
+	/// cmos.disable_alarm_interrupt();
+	/// assert_eq!(cmos.read(0x0B), 0x12);
+	/// ```
+	/// [`ack_periodic`]: struct.CMOS.html#method.ack_periodic
+	#[cfg(not(feature = "read-only"))]
+	pub fn enable_periodic_interrupt(&mut self) { self.set_register_b_bits(0x40, true); }
+
+	/// Disables the periodic interrupt (bit 6 of register B)
+	#[cfg(not(feature = "read-only"))]
+	pub fn disable_periodic_interrupt(&mut self) { self.set_register_b_bits(0x40, false); }
+
+	/// Enables the alarm interrupt (bit 5 of register B), raised when the time matches the alarm set with
+	/// [`write_alarm`].
+	///
+	/// Note: As with [`enable_periodic_interrupt`], reading register C in the IRQ8 handler and handling NMIs are up
+	/// to the caller.
+	///
+	/// [`write_alarm`]: struct.CMOS.html#method.write_alarm
+	/// [`enable_periodic_interrupt`]: struct.CMOS.html#method.enable_periodic_interrupt
+	#[cfg(not(feature = "read-only"))]
+	pub fn enable_alarm_interrupt(&mut self) { self.set_register_b_bits(0x20, true); }
+
+	/// Disables the alarm interrupt (bit 5 of register B)
+	#[cfg(not(feature = "read-only"))]
+	pub fn disable_alarm_interrupt(&mut self) { self.set_register_b_bits(0x20, false); }
+
+	/// Sets or clears `bits` in register B, keeping the other bits
+	#[cfg(not(feature = "read-only"))]
+	fn set_register_b_bits(&mut self, bits: u8, set: bool) {
+		let register_b = self.read(0x0B);
+		self.write(0x0B, if set { register_b | bits } else { register_b & !bits });
+	}
+
+	/// Acknowledges an RTC interrupt (IRQ8) and returns whether the update ended flag (bit 4 of register C) was set,
+	/// meaning the RTC just ticked over to the next second.
+	///
+	/// Note: Reading register C clears all of its pending flags, like [`ack_periodic`].
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{MockPorts, CMOS};
+	/// let mut registers = [0; 128];
+	/// registers[0x0C] = 0x10;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// assert!(cmos.on_update_ended());
+	/// // Other flags, like the periodic one, don't count
+	/// for &register_c in &[0x00, 0xC0, 0xE0] {
+	///     cmos.ports_mut().registers[0x0C] = register_c;
+	///     assert!(!cmos.on_update_ended());
+	/// }
+	/// ```
+	/// [`ack_periodic`]: struct.CMOS.html#method.ack_periodic
+	pub fn on_update_ended(&mut self) -> bool { self.read(0x0C) & 0x10 != 0 }
+
+	/// Reads whether the RTC holds local time or UTC from a flag stored in a CMOS scratch register.
+	/// The RTC itself doesn't know, so the choice is a convention of the OS: this one uses bit 0 of `flag_reg`,
+	/// set for UTC and clear for local time. Pick a register no other software on the machine uses.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{MockPorts, TimeScale, CMOS};
+	/// let mut cmos = CMOS::with_ports(MockPorts::new([0; 128]));
+	/// if cmos.time_scale(0x7E) == TimeScale::Local {
+	///     // Apply the time zone offset to get UTC
+	/// }
+	/// assert_eq!(cmos.time_scale(0x7E), TimeScale::Local);
+	/// cmos.ports_mut().registers[0x7E] = 0x01;
+	/// assert_eq!(cmos.time_scale(0x7E), TimeScale::Utc);
+	/// ```
+	pub fn time_scale(&mut self, flag_reg: u8) -> TimeScale {
+		if self.read(flag_reg) & 0x01 != 0 {
+			TimeScale::Utc
+		} else {
+			TimeScale::Local
+		}
+	}
+
+	/// Records whether the RTC holds local time or UTC in bit 0 of `flag_reg`, following the convention of
+	/// [`time_scale`]. The other bits of the register are kept.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{MockPorts, TimeScale, CMOS};
+	/// let mut registers = [0; 128];
+	/// registers[0x7E] = 0xA4;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// cmos.set_time_scale(0x7E, TimeScale::Utc);
+	/// assert_eq!(cmos.time_scale(0x7E), TimeScale::Utc);
+	/// assert_eq!(cmos.ports().registers[0x7E], 0xA5);
+	/// cmos.set_time_scale(0x7E, TimeScale::Local);
+	/// assert_eq!(cmos.time_scale(0x7E), TimeScale::Local);
+	/// assert_eq!(cmos.ports().registers[0x7E], 0xA4);
+	/// ```
+	/// [`time_scale`]: struct.CMOS.html#method.time_scale
+	#[cfg(not(feature = "read-only"))]
+	pub fn set_time_scale(&mut self, flag_reg: u8, time_scale: TimeScale) {
+		let flags = self.read(flag_reg) & !0x01;
+		match time_scale {
+			TimeScale::Local => self.write(flag_reg, flags),
+			TimeScale::Utc => self.write(flag_reg, flags | 0x01),
+		}
+	}
+
+	/// Waits for the update in progress flag to be clear, then reads the time registers.
+	/// Returns whether the flag was observed clear before the read: if it stays set for `UIP_SPIN_LIMIT` polls,
+	/// the registers are read anyway as a best effort and `false` is returned.
+	fn read_into_rtc(&mut self, raw: &mut RawRtcRegisters, delay: &mut impl FnMut()) -> bool {
+		let flag_clear = self.wait_update_in_progress_flag(false, UIP_SPIN_LIMIT, delay);
+		self.read_rtc_registers(raw);
+		flag_clear
+	}
+
+	/// Polls the update in progress flag until it is `set` (or clear), calling `delay` between polls.
+	/// Returns `false` if the flag still isn't in that state after `limit` extra polls.
+	fn wait_update_in_progress_flag(&mut self, set: bool, limit: u32, delay: &mut impl FnMut()) -> bool {
+		let mut polls = 0;
+		loop {
+			if (self.get_update_in_progress_flag() != 0) == set {
+				return true;
+			}
+			if polls == limit {
+				return false;
+			}
+			polls += 1;
+			delay();
+		}
+	}
+
+	/// Reads the time registers once, without any conversion
+	fn read_rtc_registers(&mut self, raw: &mut RawRtcRegisters) {
+		let map = self.register_map;
+		raw.second = self.read(map.second);
+		raw.minute = self.read(map.minute);
+		raw.hour = self.read(map.hour);
+		raw.day = self.read(map.day);
+		raw.month = self.read(map.month);
+		raw.year = self.read(map.year);
+	}
+
+	fn read_century(&mut self, century_handler: CMOSCenturyHandler) -> u8 {
+		match century_handler {
+			CMOSCenturyHandler::CenturyRegister(century_reg) => self.read(century_reg),
+			CMOSCenturyHandler::CurrentYear(_) => 0,
+		}
+	}
+
+	/// Reads from the RTC part of CMOS
+	/// Returns an [`RTCDateTime`] struct, which includes all date time fields.
+	/// This method automatically converts BCD to binary values and 12 hours to 24 hour if necessary.
+	/// A second of 60, read during a leap second on some hardware, is returned as 59.
+	/// If the update in progress flag never clears, as on a wedged or absent RTC, the wait is capped and the registers
+	/// are read once as a best effort instead of hanging. Rereads until two reads agree are capped the same way, for
+	/// values that never settle; [`read_rtc_with_anomaly`] reports when either happened.
+	///
+	/// # Errors
+	/// Returns [`CmosError::RtcAbsent`] if register B reads as `0xFF` or `0x00`, which is what an absent or unpowered
+	/// RTC looks like, instead of decoding a bogus [`RTCDateTime`]. [`try_read_rtc`] also checks the century handler
+	/// and the fields read.
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// # use cmos::{CMOS, CMOSCenturyHandler};
+	/// let mut cmos = unsafe { CMOS::new() };
+	/// // Get current RTC by current year of 2019
+	/// let rtc = cmos.read_rtc(CMOSCenturyHandler::CurrentYear(2019));
+	/// ```
+	///
+	/// Without an RTC, register B floats to all ones:
+	/// ```rust
+	/// # use cmos::{CMOS, CMOSCenturyHandler, CmosError, MockPorts, RTCDateTime};
+	/// let mut registers = [0; 128];
+	/// registers[..10].copy_from_slice(&[0x05, 0, 0x04, 0, 0x03, 0, 0x04, 0x02, 0x01, 0x19]);
+	/// registers[0x0B] = 0x02;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// let handler = CMOSCenturyHandler::CurrentYear(2019);
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(cmos.read_rtc(handler), Ok(datetime));
+	///
+	/// cmos.ports_mut().registers[0x0B] = 0xFF;
+	/// assert_eq!(cmos.read_rtc(handler), Err(CmosError::RtcAbsent));
+	/// cmos.ports_mut().registers[0x0B] = 0x00;
+	/// assert_eq!(cmos.read_rtc(handler), Err(CmosError::RtcAbsent));
+	/// ```
+	///
+	/// Reading from mock registers:
+	/// ```rust
+	/// # use cmos::{CMOS, CMOSCenturyHandler, MockPorts, RTCDateTime};
+	/// let mut registers = [0; 128];
+	/// // 2019-01-02T15:04:05, in BCD and 12 hour format, with the square wave on
+	/// registers[..10].copy_from_slice(&[0x05, 0, 0x04, 0, 0x83, 0, 0, 0x02, 0x01, 0x19]);
+	/// registers[0x0B] = 0x08;
+	/// registers[0x32] = 0x20;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 15, minute: 4, second: 5 };
+	/// assert_eq!(cmos.read_rtc(CMOSCenturyHandler::CenturyRegister(0x32)), Ok(datetime));
+	/// assert_eq!(cmos.read_rtc(CMOSCenturyHandler::CurrentYear(2090)), Ok(RTCDateTime { year: 2119, ..datetime }));
+	/// ```
+	///
+	/// The registers are only read once the update in progress flag is clear, so values caught mid update aren't
+	/// returned:
+	/// ```rust
+	/// # use cmos::{CMOS, CMOSCenturyHandler, MockPorts, PortIO, RTCDateTime};
+	/// // Reports an update in progress for the first polls of register A, while the time registers hold garbage
+	/// struct Updating {
+	///     ports: MockPorts,
+	///     address: u8,
+	///     polls: u32,
+	/// }
+	///
+	/// impl PortIO for Updating {
+	///     fn read(&mut self, port: u16) -> u8 {
+	///         match self.address {
+	///             0x0A if self.polls > 0 => {
+	///                 self.polls -= 1;
+	///                 0x80
+	///             },
+	///             0x00..=0x09 if self.polls > 0 => 0xFF,
+	///             _ => self.ports.read(port),
+	///         }
+	///     }
+	///
+	///     fn write(&mut self, port: u16, val: u8) {
+	///         if port == 0x70 {
+	///             self.address = val;
+	///         }
+	///         self.ports.write(port, val);
+	///     }
+	/// }
+	///
+	/// let mut registers = [0; 128];
+	/// registers[..10].copy_from_slice(&[0x05, 0, 0x04, 0, 0x03, 0, 0x04, 0x02, 0x01, 0x19]);
+	/// registers[0x0B] = 0x02;
+	/// let mut cmos = CMOS::with_ports(Updating { ports: MockPorts::new(registers), address: 0, polls: 3 });
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(cmos.read_rtc(CMOSCenturyHandler::CurrentYear(2019)), Ok(datetime));
+	/// ```
+	///
+	/// The century register is read again along with the time registers, so a century rollover in the middle of a
+	/// read isn't mixed up with the old time:
+	/// ```rust
+	/// # use cmos::{CMOS, CMOSCenturyHandler, MockPorts, PortIO, RTCDateTime};
+	/// // Ticks over from 1999-12-31T23:59:59 to 2000-01-01T00:00:00 right after the century register is first read
+	/// struct Rollover {
+	///     ports: MockPorts,
+	///     address: u8,
+	///     rolled: bool,
+	/// }
+	///
+	/// impl PortIO for Rollover {
+	///     fn read(&mut self, port: u16) -> u8 {
+	///         let value = self.ports.read(port);
+	///         if self.address == 0x32 && !self.rolled {
+	///             self.rolled = true;
+	///             self.ports.registers[..10].copy_from_slice(&[0, 0, 0, 0, 0, 0, 0x07, 0x01, 0x01, 0x00]);
+	///             self.ports.registers[0x32] = 0x20;
+	///         }
+	///         value
+	///     }
+	///
+	///     fn write(&mut self, port: u16, val: u8) {
+	///         if port == 0x70 {
+	///             self.address = val;
+	///         }
+	///         self.ports.write(port, val);
+	///     }
+	/// }
+	///
+	/// let mut registers = [0; 128];
+	/// registers[..10].copy_from_slice(&[0x59, 0, 0x59, 0, 0x23, 0, 0x06, 0x31, 0x12, 0x99]);
+	/// registers[0x0B] = 0x02;
+	/// registers[0x32] = 0x19;
+	/// let mut cmos = CMOS::with_ports(Rollover { ports: MockPorts::new(registers), address: 0, rolled: false });
+	/// // Keeping the century of the first pass would give 1900-01-01T00:00:00
+	/// let datetime = cmos.read_rtc(CMOSCenturyHandler::CenturyRegister(0x32)).unwrap();
+	/// assert_eq!(datetime, RTCDateTime { year: 2000, month: 1, day: 1, hour: 0, minute: 0, second: 0 });
+	/// ```
+	/// [`read_rtc_with_anomaly`]: struct.CMOS.html#method.read_rtc_with_anomaly
+	/// [`CmosError::RtcAbsent`]: enum.CmosError.html#variant.RtcAbsent
+	/// [`RTCDateTime`]: struct.RTCDateTime.html
+	/// [`try_read_rtc`]: struct.CMOS.html#method.try_read_rtc
+	pub fn read_rtc(&mut self, century_handler: CMOSCenturyHandler) -> Result<RTCDateTime, CmosError> {
+		match self.read(0x0B) {
+			0x00 | 0xFF => Err(CmosError::RtcAbsent),
+			_ => Ok(self.read_rtc_with_delay(century_handler, || {})),
+		}
+	}
+
+	/// Reads from the RTC part of CMOS, like [`read_rtc`], calling `delay` each time the update in progress flag
+	/// is polled and found set. Unlike [`read_rtc`], register B isn't checked for an absent RTC.
+	/// This lets cooperative schedulers yield (or sleep) instead of busy-waiting on the flag.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{CMOS, CMOSCenturyHandler, MockPorts, PortIO, RTCDateTime};
+	/// // Reports an update in progress for the first polls of register A
+	/// struct Updating {
+	///     ports: MockPorts,
+	///     address: u8,
+	///     polls: u32,
+	/// }
+	///
+	/// impl PortIO for Updating {
+	///     fn read(&mut self, port: u16) -> u8 {
+	///         if self.address == 0x0A && self.polls > 0 {
+	///             self.polls -= 1;
+	///             return 0x80;
+	///         }
+	///         self.ports.read(port)
+	///     }
+	///
+	///     fn write(&mut self, port: u16, val: u8) {
+	///         if port == 0x70 {
+	///             self.address = val;
+	///         }
+	///         self.ports.write(port, val);
+	///     }
+	/// }
+	///
+	/// let mut registers = [0; 128];
+	/// registers[..10].copy_from_slice(&[0x05, 0, 0x04, 0, 0x03, 0, 0x04, 0x02, 0x01, 0x19]);
+	/// registers[0x0B] = 0x02;
+	/// let mut cmos = CMOS::with_ports(Updating { ports: MockPorts::new(registers), address: 0, polls: 3 });
+	/// let mut delays = 0;
+	/// let rtc = cmos.read_rtc_with_delay(CMOSCenturyHandler::CurrentYear(2019), || delays += 1);
+	/// assert_eq!(rtc, RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 });
+	/// // Once per poll finding the flag set
+	/// assert_eq!(delays, 3);
+	///
+	/// // Not called at all when no update is in progress
+	/// delays = 0;
+	/// cmos.read_rtc_with_delay(CMOSCenturyHandler::CurrentYear(2019), || delays += 1);
+	/// assert_eq!(delays, 0);
+	/// ```
+	/// [`read_rtc`]: struct.CMOS.html#method.read_rtc
+	pub fn read_rtc_with_delay(&mut self, century_handler: CMOSCenturyHandler, mut delay: impl FnMut()) -> RTCDateTime {
+		let raw = self.read_rtc_raw(century_handler, &mut delay);
+		self.convert_rtc(&raw, century_handler)
+	}
+
+	/// Reads from the RTC part of CMOS, like [`read_rtc`], with interrupts disabled for the whole read.
+	/// An RTC interrupt handler (IRQ8) that accesses the CMOS in the middle of the read would change the selected
+	/// register under it, so interrupts are briefly disabled, until the values are stable, and then restored to
+	/// their previous state. This can take a few milliseconds if the read waits for an RTC update.
+	///
+	/// Only available with the `interrupts` feature.
+	///
+	/// # Examples
+	/// This example isn't run, even against [`MockPorts`]: disabling interrupts is a privileged instruction, which
+	/// faults when a doctest runs it in user space. The read itself is [`read_rtc`], which is tested against a mock.
+	/// ```rust,no_run
+	/// # use cmos::{CMOS, CMOSCenturyHandler};
+	/// let mut cmos = unsafe { CMOS::new() };
+	/// let rtc = cmos.read_rtc_irq_safe(CMOSCenturyHandler::CurrentYear(2019));
+	/// ```
+	/// [`MockPorts`]: struct.MockPorts.html
+	/// [`read_rtc`]: struct.CMOS.html#method.read_rtc
+	#[cfg(feature = "interrupts")]
+	pub fn read_rtc_irq_safe(&mut self, century_handler: CMOSCenturyHandler) -> Result<RTCDateTime, CmosError> {
+		x86_64::instructions::interrupts::without_interrupts(|| self.read_rtc(century_handler))
+	}
+
+	/// Reads from the RTC part of CMOS, like [`read_rtc`], also returning the raw value of the century register the
+	/// year was computed from. The century byte is `None` with `CurrentYear`, where no century register is read.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{CMOS, CMOSCenturyHandler, MockPorts};
+	/// let mut registers = [0; 128];
+	/// registers[..10].copy_from_slice(&[0x05, 0, 0x04, 0, 0x03, 0, 0x04, 0x02, 0x01, 0x19]);
+	/// registers[0x0B] = 0x02;
+	/// registers[0x32] = 0x20;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// let (rtc, century) = cmos.read_rtc_with_century(CMOSCenturyHandler::CenturyRegister(0x32));
+	/// assert_eq!(rtc.year, 2019);
+	/// // 0x20 in BCD for the 2000s, as stored
+	/// assert_eq!(century, Some(0x20));
+	///
+	/// let (rtc, century) = cmos.read_rtc_with_century(CMOSCenturyHandler::CurrentYear(2019));
+	/// assert_eq!((rtc.year, century), (2019, None));
+	/// ```
+	/// [`read_rtc`]: struct.CMOS.html#method.read_rtc
+	pub fn read_rtc_with_century(&mut self, century_handler: CMOSCenturyHandler) -> (RTCDateTime, Option<u8>) {
+		let raw = self.read_rtc_raw(century_handler, &mut || {});
+		let century = match century_handler {
+			CMOSCenturyHandler::CenturyRegister(_) => Some(raw.century),
+			CMOSCenturyHandler::CurrentYear(_) => None,
+		};
+		(self.convert_rtc(&raw, century_handler), century)
+	}
+
+	/// Reads from the RTC part of CMOS, like [`read_rtc`], also returning whether the read was anomalous: `true` when
+	/// the update in progress flag stayed set for the whole capped wait, so the registers were read once as a best
+	/// effort, without waiting for an update to finish nor checking that two reads agree, when consecutive reads kept
+	/// differing and the last one was kept after a capped number of rereads, and when the seconds read 60 during a
+	/// leap second, which was clamped to 59 (see [`RawRtcRegisters::is_leap_second`]).
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{CMOS, CMOSCenturyHandler, MockPorts, RTCDateTime};
+	/// let mut registers = [0; 128];
+	/// registers[..10].copy_from_slice(&[0x05, 0, 0x04, 0, 0x03, 0, 0x04, 0x02, 0x01, 0x19]);
+	/// registers[0x0B] = 0x02;
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// assert_eq!(cmos.read_rtc_with_anomaly(CMOSCenturyHandler::CurrentYear(2019)), (datetime, false));
+	///
+	/// // The update in progress flag never clears
+	/// registers[0x0A] = 0x80;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// assert_eq!(cmos.read_rtc_with_anomaly(CMOSCenturyHandler::CurrentYear(2019)), (datetime, true));
+	/// assert_eq!(cmos.read_rtc(CMOSCenturyHandler::CurrentYear(2019)), Ok(datetime));
+	/// ```
+	/// During a leap second:
+	/// ```rust
+	/// # use cmos::{CMOS, CMOSCenturyHandler, MockPorts, PortIO, RTCDateTime};
+	/// // Reads 23:59:60 for the first reads of the seconds register, then 23:59:59
+	/// struct LeapSecond {
+	///     ports: MockPorts,
+	///     address: u8,
+	///     leap_reads: u32,
+	/// }
+	///
+	/// impl PortIO for LeapSecond {
+	///     fn read(&mut self, port: u16) -> u8 {
+	///         if self.address == 0x00 && self.leap_reads > 0 {
+	///             self.leap_reads -= 1;
+	///             return 0x60;
+	///         }
+	///         self.ports.read(port)
+	///     }
+	///
+	///     fn write(&mut self, port: u16, val: u8) {
+	///         if port == 0x70 {
+	///             self.address = val;
+	///         }
+	///         self.ports.write(port, val);
+	///     }
+	/// }
+	///
+	/// let mut registers = [0; 128];
+	/// registers[..10].copy_from_slice(&[0x59, 0, 0x59, 0, 0x23, 0, 0x04, 0x02, 0x01, 0x19]);
+	/// registers[0x0B] = 0x02;
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 23, minute: 59, second: 59 };
+	/// let mut cmos = CMOS::with_ports(LeapSecond { ports: MockPorts::new(registers), address: 0, leap_reads: 2 });
+	/// // The second of 60 is clamped, and reported
+	/// assert_eq!(cmos.read_rtc_with_anomaly(CMOSCenturyHandler::CurrentYear(2019)), (datetime, true));
+	/// // The real second 59 isn't
+	/// assert_eq!(cmos.read_rtc_with_anomaly(CMOSCenturyHandler::CurrentYear(2019)), (datetime, false));
+	/// ```
+	/// On a flaky bus, where the values never settle:
+	/// ```rust
+	/// # use cmos::{CMOS, CMOSCenturyHandler, MockPorts, PortIO};
+	/// // The seconds register reads one more each time, with the update in progress flag always clear
+	/// struct Flaky {
+	///     ports: MockPorts,
+	///     address: u8,
+	///     reads: u32,
+	/// }
+	///
+	/// impl PortIO for Flaky {
+	///     fn read(&mut self, port: u16) -> u8 {
+	///         if self.address == 0x00 {
+	///             self.reads += 1;
+	///             return (self.reads % 60) as u8;
+	///         }
+	///         self.ports.read(port)
+	///     }
+	///
+	///     fn write(&mut self, port: u16, val: u8) {
+	///         if port == 0x70 {
+	///             self.address = val;
+	///         }
+	///         self.ports.write(port, val);
+	///     }
+	/// }
+	///
+	/// let mut registers = [0; 128];
+	/// // Binary, 24 hour format
+	/// registers[..10].copy_from_slice(&[0, 0, 4, 0, 3, 0, 4, 2, 1, 19]);
+	/// registers[0x0B] = 0x06;
+	/// let mut cmos = CMOS::with_ports(Flaky { ports: MockPorts::new(registers), address: 0, reads: 0 });
+	/// // The rereads are capped instead of spinning forever: the last of the 101 reads is kept, and reported
+	/// let (datetime, anomaly) = cmos.read_rtc_with_anomaly(CMOSCenturyHandler::CurrentYear(2019));
+	/// assert_eq!((datetime.as_tuple(), anomaly), ((2019, 1, 2, 3, 4, 41), true));
+	/// ```
+	/// [`read_rtc`]: struct.CMOS.html#method.read_rtc
+	/// [`RawRtcRegisters::is_leap_second`]: struct.RawRtcRegisters.html#method.is_leap_second
+	pub fn read_rtc_with_anomaly(&mut self, century_handler: CMOSCenturyHandler) -> (RTCDateTime, bool) {
+		let (raw, rereads) = self.read_rtc_raw_counted(century_handler, &mut || {});
+		(self.convert_rtc(&raw, century_handler), rereads.is_none() || raw.is_leap_second())
+	}
+
+	/// Reads from the RTC part of CMOS using the century register, falling back to `fallback_year` when the century
+	/// register can't be trusted.
+	/// The century register is not trusted when it reads `0x00` or `0xFF` (unimplemented or unpowered), or when the
+	/// resulting year is more than 50 years away from `fallback_year`. The year is then calculated like
+	/// `CMOSCenturyHandler::CurrentYear(fallback_year)` does.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{MockPorts, CMOS};
+	/// let mut registers = [0; 128];
+	/// registers[..10].copy_from_slice(&[0x05, 0, 0x04, 0, 0x03, 0, 0x04, 0x02, 0x01, 0x19]);
+	/// registers[0x0B] = 0x02;
+	/// registers[0x32] = 0x20;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// // A trustworthy century register is used
+	/// assert_eq!(cmos.read_rtc_robust(0x32, 2019).year, 2019);
+	/// assert_eq!(cmos.read_rtc_robust(0x32, 2060).year, 2019);
+	///
+	/// // Unimplemented or unpowered century registers are ignored
+	/// for &century in &[0x00, 0xFF] {
+	///     cmos.ports_mut().registers[0x32] = century;
+	///     assert_eq!(cmos.read_rtc_robust(0x32, 2019).year, 2019);
+	/// }
+	///
+	/// // So is a century register more than 50 years away from the fallback year
+	/// cmos.ports_mut().registers[0x32] = 0x19;
+	/// assert_eq!(cmos.read_rtc_robust(0x32, 1950).year, 1919);
+	/// assert_eq!(cmos.read_rtc_robust(0x32, 2019).year, 2019);
+	/// ```
+	pub fn read_rtc_robust(&mut self, century_reg: u8, fallback_year: usize) -> RTCDateTime {
+		let century_handler = CMOSCenturyHandler::CenturyRegister(century_reg);
+		let fallback_handler = CMOSCenturyHandler::CurrentYear(fallback_year);
+		let raw = self.read_rtc_raw(century_handler, &mut || {});
+		if raw.century == 0x00 || raw.century == 0xFF {
+			return self.convert_rtc(&raw, fallback_handler);
+		}
+		let converted = self.convert_rtc(&raw, century_handler);
+		if converted.year + 50 < fallback_year || converted.year > fallback_year + 50 {
+			self.convert_rtc(&raw, fallback_handler)
+		} else {
+			converted
+		}
+	}
+
+	/// Reads the time registers until two consecutive reads agree, returning them unconverted along with the century
+	/// and register B. The weekday register isn't read.
+	fn read_rtc_raw(&mut self, century_handler: CMOSCenturyHandler, delay: &mut impl FnMut()) -> RawRtcRegisters {
+		self.read_rtc_raw_counted(century_handler, delay).0
+	}
+
+	/// Reads the time registers like `read_rtc_raw`, also returning how many reads it took after the first one for
+	/// two consecutive reads to agree, or `None` if the update in progress flag never cleared or the reads never
+	/// agreed within `REREAD_LIMIT` rereads
+	fn read_rtc_raw_counted(
+		&mut self,
+		century_handler: CMOSCenturyHandler,
+		delay: &mut impl FnMut(),
+	) -> (RawRtcRegisters, Option<u32>) {
+		let mut raw = RawRtcRegisters::default();
+		let mut rereads = 0;
+
+		// Note: This uses the "read registers until you get the same values twice in a row" technique to avoid getting
+		// dodgy/inconsistent values due to RTC updates
+		let mut latched_clear = self.read_into_rtc(&mut raw, delay);
+		raw.century = self.read_century(century_handler);
+		let mut previous_clear = latched_clear;
+
+		// A flag that never clears means a wedged RTC, whose values won't settle either, and values that keep changing
+		// mean a flaky bus: either way the best effort read is kept
+		let settled = latched_clear
+			&& loop {
+				if rereads == REREAD_LIMIT {
+					break false;
+				}
+				let previous = raw;
+				previous_clear = latched_clear;
+
+				// Space the reads out so they span the update window
+				for _ in 0..self.stabilization_polls {
+					self.get_update_in_progress_flag();
+				}
+
+				// The century register is read again on every pass, so a rollover at a century boundary is caught
+				latched_clear = self.read_into_rtc(&mut raw, delay);
+				raw.century = self.read_century(century_handler);
+				rereads += 1;
+
+				if !latched_clear {
+					break false;
+				}
+				if previous == raw {
+					break true;
+				}
+			};
+
+		// Accepted values must have been latched with the flag seen clear, both by the last read and by the one it
+		// agreed with
+		debug_assert!(!settled || (previous_clear && latched_clear), "RTC read accepted while updating");
+		raw.register_b = self.read(0x0B);
+		(raw, if settled { Some(rereads) } else { None })
+	}
+
+	/// Reads from the RTC part of CMOS, like [`read_rtc`], grading how far the result can be trusted, for example to
+	/// decide between the RTC and a network time source.
+	/// The grade comes from [`Confidence::assess`], given register D's valid RAM and time bit (clear when the CMOS
+	/// battery died) and how many reads it took for the values to stabilize.
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// # use cmos::{CMOS, CMOSCenturyHandler, Confidence};
+	/// let mut cmos = unsafe { CMOS::new() };
+	/// let (rtc, confidence) = cmos.read_rtc_with_confidence(CMOSCenturyHandler::CurrentYear(2019));
+	/// if confidence != Confidence::High {
+	///     // Ask an NTP server
+	/// }
+	/// ```
+	/// [`read_rtc`]: struct.CMOS.html#method.read_rtc
+	/// [`Confidence::assess`]: enum.Confidence.html#method.assess
+	pub fn read_rtc_with_confidence(&mut self, century_handler: CMOSCenturyHandler) -> (RTCDateTime, Confidence) {
+		let valid_time = self.read(0x0D) & 0x80 != 0;
+		let (raw, rereads) = self.read_rtc_raw_counted(century_handler, &mut || {});
+		let datetime = self.convert_rtc(&raw, century_handler);
+		(datetime, Confidence::assess(&datetime, valid_time, rereads))
+	}
+
+	/// Writes a human readable report of the RTC state into `buf`, for bug reports: the current time, registers A to
+	/// D with their bits decoded, the data format and the [`Confidence`] in the time.
+	/// Returns the number of bytes written. The report takes about 400 bytes, and is cut short if `buf` is smaller.
+	///
+	/// Note: Reading register C acknowledges the pending RTC interrupts.
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// # use cmos::{CMOS, CMOSCenturyHandler};
+	/// let mut cmos = unsafe { CMOS::new() };
+	/// let mut buf = [0; 512];
+	/// let len = cmos.rtc_report(CMOSCenturyHandler::CurrentYear(2019), &mut buf);
+	/// let report = core::str::from_utf8(&buf[..len]).unwrap();
+	/// ```
+	///
+	/// Reporting on mock registers:
+	/// ```rust
+	/// # use cmos::{CMOS, CMOSCenturyHandler, MockPorts};
+	/// let mut registers = [0; 128];
+	/// registers[..10].copy_from_slice(&[0x05, 0, 0x04, 0, 0x03, 0, 0x04, 0x02, 0x01, 0x19]);
+	/// registers[0x0A] = 0x26;
+	/// registers[0x0B] = 0x02;
+	/// registers[0x0D] = 0x80;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// let mut buf = [0; 512];
+	/// let len = cmos.rtc_report(CMOSCenturyHandler::CurrentYear(2019), &mut buf);
+	/// let report = core::str::from_utf8(&buf[..len]).unwrap();
+	/// assert!(report.contains("current time: 2019-01-02T03:04:05Z\n"));
+	/// assert!(report.contains("register A: 0x26 (update in progress: no, divider: 2, rate: 6)\n"));
+	/// assert!(report.contains("24 hour: yes"));
+	/// assert!(report.contains("register D: 0x80 (valid RAM and time: yes)\n"));
+	/// assert!(report.contains("format: BCD, 24 hour\n"));
+	/// assert!(report.ends_with("confidence: High\n"));
+	///
+	/// let len = cmos.rtc_report(CMOSCenturyHandler::CurrentYear(2019), &mut buf[..40]);
+	/// assert_eq!(&buf[..len], b"current time: 2019-01-02T03:04:05Z\n");
+	/// ```
+	/// [`Confidence`]: enum.Confidence.html
+	pub fn rtc_report(&mut self, century_handler: CMOSCenturyHandler, buf: &mut [u8]) -> usize {
+		let (a, b, c, d) = (self.read(0x0A), self.read(0x0B), self.read(0x0C), self.read(0x0D));
+		let (datetime, confidence) = self.read_rtc_with_confidence(century_handler);
+		let format = RtcFormat::from_register_b(b);
+		let flag = |value: u8, bit: u8| if value & bit != 0 { "yes" } else { "no" };
+
+		let mut writer = SliceWriter::new(buf);
+		// A full buffer only cuts the report short
+		let _ = (|| -> fmt::Result {
+			writeln!(writer, "current time: {}", datetime)?;
+			writeln!(
+				writer,
+				"register A: {:#04X} (update in progress: {}, divider: {}, rate: {})",
+				a,
+				flag(a, 0x80),
+				(a >> 4) & 0x07,
+				a & 0x0F
+			)?;
+			writeln!(
+				writer,
+				"register B: {:#04X} (set: {}, periodic interrupt: {}, alarm interrupt: {}, update ended interrupt: {}, \
+				 square wave: {}, binary: {}, 24 hour: {}, daylight saving: {})",
+				b,
+				flag(b, 0x80),
+				flag(b, 0x40),
+				flag(b, 0x20),
+				flag(b, 0x10),
+				flag(b, 0x08),
+				flag(b, 0x04),
+				flag(b, 0x02),
+				flag(b, 0x01)
+			)?;
+			writeln!(
+				writer,
+				"register C: {:#04X} (interrupt request: {}, periodic: {}, alarm: {}, update ended: {})",
+				c,
+				flag(c, 0x80),
+				flag(c, 0x40),
+				flag(c, 0x20),
+				flag(c, 0x10)
+			)?;
+			writeln!(writer, "register D: {:#04X} (valid RAM and time: {})", d, flag(d, 0x80))?;
+			writeln!(
+				writer,
+				"format: {}, {}",
+				if format.binary { "binary" } else { "BCD" },
+				if format.hour_24 { "24 hour" } else { "12 hour" }
+			)?;
+			writeln!(writer, "confidence: {:?}", confidence)
+		})();
+		writer.len()
+	}
+
+	/// Reads from the RTC part of CMOS right after a second boundary, pairing the result with a monotonic tick count
+	/// sampled at that boundary.
+	/// This waits for the update in progress flag to be set and then cleared again, which happens when the RTC ticks
+	/// over to the next second, then samples `ticks` straight away. The returned tick count is therefore the
+	/// calibration point of the whole second read, for disciplining a clock: the sub-second time is interpolated
+	/// from the tick source, counting from that point.
+	///
+	/// Note: This blocks for up to a second, until the next RTC update. Returns `None` if the update in progress
+	/// flag doesn't toggle, on an absent or wedged RTC: it is only polled for a bounded number of times, so the flag
+	/// being stuck at 0 or 1 can't hang the caller.
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// # use cmos::{CMOS, CMOSCenturyHandler};
+	/// # fn read_tsc() -> u64 { 0 }
+	/// let mut cmos = unsafe { CMOS::new() };
+	/// if let Some((rtc, tsc)) = cmos.read_rtc_with_ticks(CMOSCenturyHandler::CurrentYear(2019), read_tsc) {
+	///     // Calibrate the TSC against the RTC
+	/// }
+	/// ```
+	/// Against mocks, one ticking over, and one whose update in progress flag never toggles:
+	/// ```rust
+	/// # use cmos::{CMOS, CMOSCenturyHandler, MockPorts, PortIO, RTCDateTime};
+	/// // Reports an update in progress on the 3rd to 5th polls of register A
+	/// struct Ticking {
+	///     ports: MockPorts,
+	///     address: u8,
+	///     polls: u32,
+	/// }
+	///
+	/// impl PortIO for Ticking {
+	///     fn read(&mut self, port: u16) -> u8 {
+	///         if self.address != 0x0A {
+	///             return self.ports.read(port);
+	///         }
+	///         self.polls += 1;
+	///         if (3..6).contains(&self.polls) { 0x80 } else { 0 }
+	///     }
+	///
+	///     fn write(&mut self, port: u16, val: u8) {
+	///         if port == 0x70 {
+	///             self.address = val;
+	///         }
+	///         self.ports.write(port, val);
+	///     }
+	/// }
+	///
+	/// let mut registers = [0; 128];
+	/// registers[..10].copy_from_slice(&[0x05, 0, 0x04, 0, 0x03, 0, 0x04, 0x02, 0x01, 0x19]);
+	/// registers[0x0B] = 0x02;
+	/// let mut cmos = CMOS::with_ports(Ticking { ports: MockPorts::new(registers), address: 0, polls: 0 });
+	/// let (rtc, ticks) = cmos.read_rtc_with_ticks(CMOSCenturyHandler::CurrentYear(2019), || 42).unwrap();
+	/// assert_eq!(rtc, RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 });
+	/// assert_eq!(ticks, 42);
+	/// // The ticks were sampled as soon as the flag cleared
+	/// assert_eq!(cmos.ports().polls, 6);
+	///
+	/// let mut cmos = CMOS::with_ports(MockPorts::new([0; 128]));
+	/// assert_eq!(cmos.read_rtc_with_ticks(CMOSCenturyHandler::CurrentYear(2019), || 42), None);
+	/// // Stuck updating
+	/// cmos.ports_mut().registers[0x0A] = 0x80;
+	/// assert_eq!(cmos.read_rtc_with_ticks(CMOSCenturyHandler::CurrentYear(2019), || 42), None);
+	/// ```
+	pub fn read_rtc_with_ticks(
+		&mut self,
+		century_handler: CMOSCenturyHandler,
+		mut ticks: impl FnMut() -> u64,
+	) -> Option<(RTCDateTime, u64)> {
+		if !self.wait_update_in_progress_flag(true, UPDATE_SPIN_LIMIT, &mut || {})
+			|| !self.wait_update_in_progress_flag(false, UIP_SPIN_LIMIT, &mut || {})
+		{
+			return None;
+		}
+		let boundary_ticks = ticks();
+
+		// The registers won't change again for almost a second, so a single pass is consistent
+		let mut raw = RawRtcRegisters::default();
+		self.read_rtc_registers(&mut raw);
+		raw.century = self.read_century(century_handler);
+		raw.register_b = self.read(0x0B);
+		Some((self.convert_rtc(&raw, century_handler), boundary_ticks))
+	}
+
+	/// Reads from the RTC part of CMOS in a single pass, never waiting on the update in progress flag
+	/// and never looping, so it can't block.
+	/// Because of that, it may return an inconsistent value if the RTC was updating during the read: it is meant for
+	/// places where blocking is unacceptable, like timestamping in a double fault or NMI handler.
+	/// Use [`read_rtc`] everywhere else.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{CMOS, CMOSCenturyHandler, MockPorts, PortIO, RTCDateTime};
+	/// // Counts the register selections
+	/// struct Counting(MockPorts, u32);
+	///
+	/// impl PortIO for Counting {
+	///     fn read(&mut self, port: u16) -> u8 { self.0.read(port) }
+	///     fn write(&mut self, port: u16, val: u8) {
+	///         if port == 0x70 {
+	///             self.1 += 1;
+	///         }
+	///         self.0.write(port, val);
+	///     }
+	/// }
+	///
+	/// let mut registers = [0; 128];
+	/// registers[..10].copy_from_slice(&[0x05, 0, 0x04, 0, 0x03, 0, 0x04, 0x02, 0x01, 0x19]);
+	/// // An update in progress, forever
+	/// registers[0x0A] = 0x80;
+	/// registers[0x0B] = 0x02;
+	/// let mut cmos = CMOS::with_ports(Counting(MockPorts::new(registers), 0));
+	/// // Inside a fault handler
+	/// let crash_time = cmos.read_rtc_unchecked(CMOSCenturyHandler::CurrentYear(2019));
+	/// assert_eq!(crash_time, RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 });
+	/// // Straight away: the 6 time registers and register B, without polling register A
+	/// assert_eq!(cmos.ports().1, 7);
+	/// ```
+	/// [`read_rtc`]: struct.CMOS.html#method.read_rtc
+	pub fn read_rtc_unchecked(&mut self, century_handler: CMOSCenturyHandler) -> RTCDateTime {
+		let mut raw = RawRtcRegisters::default();
+		self.read_rtc_registers(&mut raw);
+		raw.century = self.read_century(century_handler);
+		raw.register_b = self.read(0x0B);
+		self.convert_rtc(&raw, century_handler)
+	}
+
+	/// Reads the raw RTC time registers along with register B, without any conversion.
+	/// The values can be written back verbatim with [`write_rtc_passthrough`], for example to mirror one RTC to another.
+	/// The century register isn't read, so the `century` field is 0.
+	/// Like [`read_rtc`], the registers are read once the update in progress flag is clear, or anyway if it stays set
+	/// for too long.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{MockPorts, CMOS};
+	/// let mut registers = [0; 128];
+	/// registers[..10].copy_from_slice(&[0x05, 0, 0x04, 0, 0x03, 0, 0x04, 0x02, 0x01, 0x19]);
+	/// registers[0x0B] = 0x02;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// let raw = cmos.read_rtc_passthrough();
+	/// // Bit 2 of register B tells whether the values are BCD or binary
+	/// assert_eq!(raw.register_b & 0x04, 0);
+	/// assert_eq!((raw.hour, raw.minute, raw.second), (0x03, 0x04, 0x05));
+	/// assert_eq!((raw.year, raw.month, raw.day, raw.weekday), (0x19, 0x01, 0x02, 0x04));
+	///
+	/// // A wedged update in progress flag doesn't hang the read
+	/// cmos.ports_mut().registers[0x0A] = 0x80;
+	/// assert_eq!(cmos.read_rtc_passthrough(), raw);
+	/// ```
+	/// [`write_rtc_passthrough`]: struct.CMOS.html#method.write_rtc_passthrough
+	/// [`read_rtc`]: struct.CMOS.html#method.read_rtc
+	pub fn read_rtc_passthrough(&mut self) -> RawRtcRegisters {
+		let mut raw = RawRtcRegisters::default();
+		self.read_into_rtc(&mut raw, &mut || {});
+		raw.weekday = self.read(self.register_map.weekday);
+		raw.register_b = self.read(0x0B);
+		raw
+	}
+
+	/// Writes raw RTC time registers and register B verbatim, as returned by [`read_rtc_passthrough`].
+	/// RTC updates are halted (through bit 7 of register B) while the time registers are written.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{MockPorts, CMOS};
+	/// let mut registers = [0; 128];
+	/// // Binary, 12 hour format: 3 PM
+	/// registers[..10].copy_from_slice(&[5, 0, 4, 0, 0x83, 0, 4, 2, 1, 19]);
+	/// registers[0x0B] = 0x04;
+	/// let mut source = CMOS::with_ports(MockPorts::new(registers));
+	/// let mut target = CMOS::with_ports(MockPorts::new([0; 128]));
+	/// // Mirror one RTC to another without any conversion
+	/// let raw = source.read_rtc_passthrough();
+	/// target.write_rtc_passthrough(&raw);
+	/// assert_eq!(&target.ports().registers[..10], &source.ports().registers[..10]);
+	/// assert_eq!(target.ports().registers[0x0B], 0x04);
+	/// ```
+	/// [`read_rtc_passthrough`]: struct.CMOS.html#method.read_rtc_passthrough
+	#[cfg(not(feature = "read-only"))]
+	pub fn write_rtc_passthrough(&mut self, raw: &RawRtcRegisters) {
+		let map = self.register_map;
+		self.write(0x0B, raw.register_b | 0x80);
+		self.write(map.second, raw.second);
+		self.write(map.minute, raw.minute);
+		self.write(map.hour, raw.hour);
+		self.write(map.weekday, raw.weekday);
+		self.write(map.day, raw.day);
+		self.write(map.month, raw.month);
+		self.write(map.year, raw.year);
+		self.write(0x0B, raw.register_b);
+	}
+
+	/// Writes an [`RTCDateTime`] to the RTC part of CMOS, in the format currently configured in register B.
+	/// The weekday register is set from the date, and with `CenturyRegister` the century register is written too.
+	/// RTC updates are halted while the registers are written.
+	///
+	/// Returns [`CmosError::InvalidField`] without writing anything if a field is out of range, or if the year
+	/// can't be represented: it must be at most 9999 with `CenturyRegister`, and within the window
+	/// [`CMOSCenturyHandler::full_year`] reads back with `CurrentYear`.
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// # use cmos::{CMOS, CMOSCenturyHandler, RTCDateTime};
+	/// let mut cmos = unsafe { CMOS::new() };
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// cmos.write_rtc(&datetime, CMOSCenturyHandler::CurrentYear(2019)).unwrap();
+	/// ```
+	///
+	/// Writing to mock registers:
+	/// ```rust
+	/// # use cmos::{CMOS, CMOSCenturyHandler, MockPorts, RTCDateTime};
+	/// let mut registers = [0; 128];
+	/// // BCD and 24 hour format
+	/// registers[0x0B] = 0x02;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// cmos.write_rtc(&datetime, CMOSCenturyHandler::CenturyRegister(0x32)).unwrap();
+	/// assert_eq!(cmos.ports().registers[..10], [0x05, 0, 0x04, 0, 0x03, 0, 0x04, 0x02, 0x01, 0x19]);
+	/// assert_eq!(cmos.ports().registers[0x32], 0x20);
+	/// assert_eq!(cmos.ports().registers[0x0B], 0x02);
+	/// assert_eq!(cmos.read_rtc(CMOSCenturyHandler::CenturyRegister(0x32)), Ok(datetime));
+	/// ```
+	/// [`RTCDateTime`]: struct.RTCDateTime.html
+	/// [`CmosError::InvalidField`]: enum.CmosError.html#variant.InvalidField
+	/// [`CMOSCenturyHandler::full_year`]: enum.CMOSCenturyHandler.html#method.full_year
+	#[cfg(not(feature = "read-only"))]
+	pub fn write_rtc(&mut self, datetime: &RTCDateTime, century_handler: CMOSCenturyHandler) -> Result<(), CmosError> {
+		if let Some(field) = datetime.invalid_field() {
+			return Err(CmosError::InvalidField(field));
+		}
+		let representable = match century_handler {
+			CMOSCenturyHandler::CenturyRegister(_) => datetime.year <= 9999,
+			CMOSCenturyHandler::CurrentYear(_) => century_handler.full_year((datetime.year % 100) as u8, 0) == datetime.year,
+		};
+		if !representable {
+			return Err(CmosError::InvalidField(RtcField::Year));
+		}
+
+		let register_b = self.read(0x0B) & !0x80;
+		let mut raw = datetime.encode(RtcFormat::from_register_b(register_b));
+		raw.register_b = register_b;
+		self.write(0x0B, register_b | 0x80);
+		if let CMOSCenturyHandler::CenturyRegister(century_reg) = century_handler {
+			self.write(century_reg, raw.century);
+		}
+		self.write_rtc_passthrough(&raw);
+		Ok(())
+	}
+
+	/// Writes an [`RTCDateTime`] like [`write_rtc`], then reads it back to check that the write took.
+	/// Returns [`CmosError::WriteVerifyFailed`] if the date time read back isn't the one written, or one second
+	/// later to allow for the RTC ticking in between. The read back ignores the offset set with [`with_offset`].
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{CMOS, CMOSCenturyHandler, MockPorts, RTCDateTime};
+	/// let mut registers = [0; 128];
+	/// registers[0x0B] = 0x02;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// cmos.write_rtc_verified(&datetime, CMOSCenturyHandler::CurrentYear(2019)).unwrap();
+	/// assert_eq!(&cmos.ports().registers[..10], &[0x05, 0, 0x04, 0, 0x03, 0, 0x04, 0x02, 0x01, 0x19]);
+	/// ```
+	///
+	/// A write that doesn't take is reported:
+	/// ```rust
+	/// # use cmos::{CMOS, CMOSCenturyHandler, CmosError, MockPorts, PortIO, RTCDateTime};
+	/// // Drops every write to the data port, like an RTC stuck with its battery dead
+	/// struct DroppingWrites(MockPorts);
+	///
+	/// impl PortIO for DroppingWrites {
+	///     fn read(&mut self, port: u16) -> u8 { self.0.read(port) }
+	///
+	///     fn write(&mut self, port: u16, val: u8) {
+	///         if port != 0x71 {
+	///             self.0.write(port, val);
+	///         }
+	///     }
+	/// }
+	///
+	/// let mut registers = [0; 128];
+	/// registers[..10].copy_from_slice(&[0x05, 0, 0x04, 0, 0x03, 0, 0x04, 0x02, 0x01, 0x19]);
+	/// registers[0x0B] = 0x02;
+	/// let mut cmos = CMOS::with_ports(DroppingWrites(MockPorts::new(registers)));
+	/// let datetime = RTCDateTime { year: 2019, month: 6, day: 7, hour: 8, minute: 9, second: 10 };
+	/// let result = cmos.write_rtc_verified(&datetime, CMOSCenturyHandler::CurrentYear(2019));
+	/// assert_eq!(result, Err(CmosError::WriteVerifyFailed));
+	/// ```
+	/// [`RTCDateTime`]: struct.RTCDateTime.html
+	/// [`write_rtc`]: struct.CMOS.html#method.write_rtc
+	/// [`CmosError::WriteVerifyFailed`]: enum.CmosError.html#variant.WriteVerifyFailed
+	/// [`with_offset`]: struct.CMOS.html#method.with_offset
+	#[cfg(not(feature = "read-only"))]
+	pub fn write_rtc_verified(
+		&mut self,
+		datetime: &RTCDateTime,
+		century_handler: CMOSCenturyHandler,
+	) -> Result<(), CmosError> {
+		self.write_rtc(datetime, century_handler)?;
+		let readback = self.read_rtc_raw(century_handler, &mut || {}).convert(century_handler);
+		match (readback.invalid_field(), readback.difference_in_seconds(datetime)) {
+			(None, 0..=1) => Ok(()),
+			_ => Err(CmosError::WriteVerifyFailed),
+		}
+	}
+
+	/// Reads from the RTC part of CMOS, like [`read_rtc`], with more checks on what is read.
+	/// Returns [`CmosError::RtcAbsent`] if register B reads as `0xFF` or `0x00`, which is what an absent or
+	/// unpowered RTC looks like, instead of decoding a bogus [`RTCDateTime`].
+	/// The century handler is checked with [`CMOSCenturyHandler::validate`] first, and the date time read is checked
+	/// with [`RTCDateTime::is_valid`]: [`CmosError::InvalidField`] is returned with the first field out of range, for
+	/// example a month of 0 read from flaky hardware.
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// # use cmos::{CMOS, CMOSCenturyHandler, CmosError};
+	/// let mut cmos = unsafe { CMOS::new() };
+	/// match cmos.try_read_rtc(CMOSCenturyHandler::CurrentYear(2019)) {
+	///     Ok(rtc) => { /* use rtc */ },
+	///     Err(CmosError::RtcAbsent) => { /* fall back to another time source */ },
+	///     Err(CmosError::InvalidField(field)) => { /* the RTC holds garbage, set it */ },
+	///     Err(error) => { /* misconfigured handler */ },
+	/// }
+	/// ```
+	///
+	/// Reading from mock registers:
+	/// ```rust
+	/// # use cmos::{CMOS, CMOSCenturyHandler, CmosError, MockPorts, RTCDateTime, RtcField};
+	/// let mut registers = [0; 128];
+	/// registers[..10].copy_from_slice(&[0x05, 0, 0x04, 0, 0x03, 0, 0x04, 0x02, 0x01, 0x19]);
+	/// registers[0x0B] = 0x02;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// let handler = CMOSCenturyHandler::CurrentYear(2019);
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(cmos.try_read_rtc(handler), Ok(datetime));
+	///
+	/// cmos.ports_mut().registers[0x08] = 0x00;
+	/// assert_eq!(cmos.try_read_rtc(handler), Err(CmosError::InvalidField(RtcField::Month)));
+	/// cmos.ports_mut().registers[0x0B] = 0xFF;
+	/// assert_eq!(cmos.try_read_rtc(handler), Err(CmosError::RtcAbsent));
+	/// ```
+	/// [`read_rtc`]: struct.CMOS.html#method.read_rtc
+	/// [`CmosError::RtcAbsent`]: enum.CmosError.html#variant.RtcAbsent
+	/// [`RTCDateTime::is_valid`]: struct.RTCDateTime.html#method.is_valid
+	/// [`CmosError::InvalidField`]: enum.CmosError.html#variant.InvalidField
+	/// [`CMOSCenturyHandler::validate`]: enum.CMOSCenturyHandler.html#method.validate
+	/// [`RTCDateTime`]: struct.RTCDateTime.html
+	pub fn try_read_rtc(&mut self, century_handler: CMOSCenturyHandler) -> Result<RTCDateTime, CmosError> {
+		century_handler.validate()?;
+		let datetime = self.read_rtc(century_handler)?;
+		match datetime.invalid_field() {
+			Some(field) => Err(CmosError::InvalidField(field)),
+			None => Ok(datetime),
+		}
+	}
+}
@@ -10,332 +10,873 @@ Some RTC chips support calculating the correct year through a century register,
 This can be done by querying the Fixed ACPI Description Table. More details can be found [here](https://wiki.osdev.org/CMOS#Century_Register).
 This library also supports providing the current year, through the [`CMOSCenturyHandler`] enum as a fallback.
 
+### Features
+The [`CMOS`] struct uses x86 port I/O and is only available with the `x86` feature, which is enabled by default.
+Building with `default-features = false` leaves out the hardware access, so the [`RTCDateTime`] type and its
+calendar logic can be used on any target:
+```rust
+# #[cfg(not(any(feature = "x86", feature = "x86_64-port")))] {
+// Built with `cargo test --no-default-features`, only the calendar logic is there
+use cmos::{RTCDateTime, Weekday};
+let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+assert!(datetime.is_valid());
+assert_eq!(datetime.weekday(), Weekday::Wednesday);
+# }
+```
+
+The port I/O is done through the `cpuio` crate by default, which keeps backward compatibility but only builds on old
+nightly compilers. Enabling the `x86_64-port` feature (with `default-features = false`) uses the `x86_64` crate's
+`Port` instead, which builds on current compilers at the cost of a heavier dependency.
+Either way, the [`CMOS`] struct only accesses the ports through the [`PortIO`] trait, and [`MockPorts`] serves the
+registers from an array instead, to exercise the RTC logic in tests.
+
+The `interrupts` feature adds [`CMOS::read_rtc_irq_safe`], which reads the RTC with interrupts disabled.
+The `serde` feature implements serde's `Serialize` and `Deserialize` for [`RTCDateTime`].
+The `chrono` feature implements `TryFrom` between [`RTCDateTime`] and `chrono::NaiveDateTime`, both ways.
+chrono is built without its default features, which need `std`: its naive types don't allocate, so they stay
+usable in `no_std`. Enabling chrono's `std` or `alloc` features is left to the dependent crate.
+The `time` feature does the same with `time::PrimitiveDateTime`, also without the time crate's default features.
+
+The `read-only` feature compiles out every method writing to the CMOS: [`CMOS::write`], `write_all`, the `write_rtc`
+family, `write_alarm`, `write_shutdown_status`, `write_checksum`, `update_checksum`, the interrupt enable and
+disable methods, `set_time_scale` and `RtcConfigBuilder::apply`. With it, a write through this crate is a compile
+error, guaranteeing that it never modifies the CMOS. Only the address port is written, to select the registers read.
+Setters that only configure the [`CMOS`] struct itself, like `set_register_map`, are kept.
+
 ## Examples
 To get the current RTC time using the current year:
 ```rust,no_run
+# #[cfg(any(feature = "x86", feature = "x86_64-port"))] {
 # use cmos::{CMOS, CMOSCenturyHandler};
 // Create a CMOS object (unsafe due to the use of port I/O)
 let mut cmos = unsafe { CMOS::new() };
 // Read the rtc date time using this year
 let rtc = cmos.read_rtc(CMOSCenturyHandler::CurrentYear(2019));
+# }
 ```
 
 To get the current RTC tiem by passing in the century register num:
 ```rust,no_run
+# #[cfg(any(feature = "x86", feature = "x86_64-port"))] {
 # use cmos::{CMOS, CMOSCenturyHandler};
 // Create a CMOS object (unsafe due to the use of port I/O)
 let mut cmos = unsafe { CMOS::new() };
 // Read the rtc date time using this year
 let rtc = cmos.read_rtc(CMOSCenturyHandler::CenturyRegister(0xA5));
+# }
+```
+
+## Round trip
+The central guarantee of the crate is that writing a valid [`RTCDateTime`] with `CMOS::write_rtc` and reading it
+back with `CMOS::read_rtc` gives the same date time, in every data format register B can select (BCD or binary,
+12 or 24 hour) and with either century handler, as long as the year can be stored: up to 9999 with a century
+register, within the window of the current year otherwise.
+```rust
+# #[cfg(all(any(feature = "x86", feature = "x86_64-port"), not(feature = "read-only")))] {
+# use cmos::{CMOSCenturyHandler, MockPorts, RTCDateTime, RtcFormat, CMOS};
+let handlers = [CMOSCenturyHandler::CenturyRegister(0x32), CMOSCenturyHandler::CurrentYear(2019)];
+let dates = [(1970, 1, 1), (1999, 12, 31), (2000, 2, 29), (2019, 6, 15), (2068, 12, 31)];
+for &binary in &[false, true] {
+    for &hour_24 in &[false, true] {
+        for &handler in &handlers {
+            let mut registers = [0; 128];
+            // The square wave keeps register B from reading as 0x00, which looks like an absent RTC
+            registers[0x0B] = RtcFormat { binary, hour_24 }.register_b_bits() | 0x08;
+            let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+            for &(year, month, day) in &dates {
+                for hour in 0..24 {
+                    let datetime = RTCDateTime { year, month, day, hour, minute: hour * 2, second: 59 - hour };
+                    cmos.write_rtc(&datetime, handler).unwrap();
+                    assert_eq!(cmos.read_rtc(handler), Ok(datetime));
+                }
+            }
+        }
+    }
+}
+# }
 ```
 
 [`CMOSCenturyHandler`]: enum.CMOSCenturyHandler.html
+[`CMOS`]: struct.CMOS.html
+[`CMOS::read_rtc_irq_safe`]: struct.CMOS.html#method.read_rtc_irq_safe
+[`CMOS::write`]: struct.CMOS.html#method.write
+[`PortIO`]: trait.PortIO.html
+[`MockPorts`]: struct.MockPorts.html
+[`RTCDateTime`]: struct.RTCDateTime.html
 */
 
 #![no_std]
 
-use cpuio::Port;
-use core::{
-	cmp::Ordering,
-	fmt::{Display, Formatter, Result},
-	usize,
+#[cfg(any(feature = "x86", feature = "x86_64-port"))]
+mod boot_clock;
+mod buf;
+#[cfg(any(feature = "x86", feature = "x86_64-port"))]
+mod cmos;
+#[cfg(any(feature = "x86", feature = "x86_64-port"))]
+mod port;
+mod rtcdatetime;
+
+#[cfg(any(feature = "x86", feature = "x86_64-port"))]
+pub use crate::boot_clock::BootClock;
+#[cfg(any(feature = "x86", feature = "x86_64-port"))]
+pub use crate::cmos::{ALARM_DONT_CARE, CMOS};
+#[cfg(any(feature = "x86", feature = "x86_64-port"))]
+pub use crate::port::{HardwarePorts, MockPorts, PortIO};
+pub use crate::rtcdatetime::{
+	is_sorted_chronological, sort_chronological, sort_chronological_unstable, CRTCDateTime, DayIter, Hemisphere, Month,
+	RTCDateTime, RtcDuration, Schedule, Season, Weekday, MAX, MIN, UNSET_YEARS,
 };
 
-/// The standard CMOS struct
-#[derive(Debug)]
-pub struct CMOS {
-	address_port: Port<u8>,
-	data_port: Port<u8>,
+use core::fmt::{self, Display, Formatter};
+
+/// Enum for determining how to calculate the year when reading the RTC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CMOSCenturyHandler {
+	/// This option is for providing the number of the century register in the RTC
+	CenturyRegister(u8),
+	/// This option is for providing the current year as a backup
+	CurrentYear(usize),
 }
 
-/// Implements the CMOS struct
-impl CMOS {
-	/// Create a new CMOS struct
+/// Offset of the `CENTURY` field in the ACPI FADT
+const FADT_CENTURY_OFFSET: usize = 0x6C;
+
+impl CMOSCenturyHandler {
+	/// Calculates the full year from the two digit year of the RTC, in binary.
+	///
+	/// With `CenturyRegister`, `century` is the (binary) value of the century register.
+	/// With `CurrentYear`, `century` is ignored and the year is picked in the 100 year window from 50 years before
+	/// to 49 years after the current year, so an RTC year of 99 is 1999 in 2000 and an RTC year of 00 is 2100 in 2099.
 	///
-	/// Note: This function is unsafe due to the creation of port I/O
 	/// # Examples
-	/// ```rust,no_run
-	/// # use cmos::{CMOS, CMOSCenturyHandler};
-	/// let mut cmos = unsafe { CMOS::new() };
+	/// ```rust
+	/// # use cmos::CMOSCenturyHandler;
+	/// assert_eq!(CMOSCenturyHandler::CenturyRegister(0x32).full_year(19, 20), 2019);
+	///
+	/// let full_year = |current_year, year| CMOSCenturyHandler::CurrentYear(current_year).full_year(year, 0);
+	/// assert_eq!(full_year(2019, 19), 2019);
+	/// assert_eq!(full_year(2019, 18), 2018);
+	/// assert_eq!(full_year(2019, 20), 2020);
+	/// assert_eq!(full_year(1999, 0), 2000);
+	/// assert_eq!(full_year(2000, 99), 1999);
+	/// assert_eq!(full_year(2000, 0), 2000);
+	/// assert_eq!(full_year(2000, 49), 2049);
+	/// assert_eq!(full_year(2000, 50), 1950);
+	/// assert_eq!(full_year(2099, 0), 2100);
+	/// assert_eq!(full_year(2099, 99), 2099);
+	/// assert_eq!(full_year(2100, 99), 2099);
+	/// assert_eq!(full_year(20, 70), 70);
 	/// ```
-	pub unsafe fn new() -> CMOS { CMOS { address_port: Port::<u8>::new(0x70), data_port: Port::<u8>::new(0x71) } }
+	pub fn full_year(&self, year: u8, century: u8) -> usize {
+		match *self {
+			CMOSCenturyHandler::CenturyRegister(_) => century as usize * 100 + year as usize,
+			CMOSCenturyHandler::CurrentYear(current_year) => {
+				let window_start = current_year.saturating_sub(50);
+				let full_year = (window_start / 100) * 100 + year as usize;
+				if full_year < window_start {
+					full_year + 100
+				} else {
+					full_year
+				}
+			},
+		}
+	}
 
-	/// Reads all the registers in CMOS
+	/// Checks the handler before it is used to read the RTC.
+	/// A century register past the end of the CMOS (`0x7F`) and a current year of 0 are rejected.
+	///
 	/// # Examples
-	/// ```rust,no_run
-	/// # use cmos::{CMOS, CMOSCenturyHandler};
-	/// let mut cmos = unsafe { CMOS::new() };
-	/// // Blank array to read into
-	/// let mut cmos_values: [u8; 128] = [0; 128];
-	/// // Read values into provided array
-	/// cmos.read_all(&mut cmos_values);
+	/// ```rust
+	/// # use cmos::{CMOSCenturyHandler, CmosError};
+	/// assert_eq!(CMOSCenturyHandler::CenturyRegister(0x32).validate(), Ok(()));
+	/// assert_eq!(CMOSCenturyHandler::CenturyRegister(0x80).validate(), Err(CmosError::InvalidCenturyRegister(0x80)));
+	/// assert_eq!(CMOSCenturyHandler::CurrentYear(2019).validate(), Ok(()));
+	/// assert_eq!(CMOSCenturyHandler::CurrentYear(0).validate(), Err(CmosError::InvalidCurrentYear(0)));
 	/// ```
-	pub fn read_all(&mut self, output: &mut [u8; 128]) {
-		for i in 0..128 {
-			self.address_port.write(i);
-			output[i as usize] = self.data_port.read();
+	pub fn validate(&self) -> Result<(), CmosError> {
+		match *self {
+			CMOSCenturyHandler::CenturyRegister(reg) if reg > 0x7F => Err(CmosError::InvalidCenturyRegister(reg)),
+			CMOSCenturyHandler::CurrentYear(0) => Err(CmosError::InvalidCurrentYear(0)),
+			_ => Ok(()),
 		}
 	}
 
-	/// Writes to all the registers in CMOS
+	/// Picks the handler from the ACPI FADT, given as the raw table bytes (header included).
+	///
+	/// The century register number is the `CENTURY` byte at offset 108 (`0x6C`) of the FADT.
+	/// If it is zero, the firmware doesn't report a century register and `CurrentYear(current_year)` is returned.
+	/// A table too short to hold the field is treated the same way.
+	///
 	/// # Examples
-	/// Writes all 0's, probably not a best idea to actually do this
-	/// ```rust,no_run
-	/// # use cmos::{CMOS, CMOSCenturyHandler};
-	/// let mut cmos = unsafe { CMOS::new() };
-	/// // Example values to write (don't do this!)
-	/// let values: [u8; 128] = [0; 128];
-	/// // Writes values to all CMOS registers
-	/// cmos.write_all(&values);
+	/// ```rust
+	/// # use cmos::CMOSCenturyHandler;
+	/// let mut fadt = [0u8; 116];
+	/// fadt[..4].copy_from_slice(b"FACP");
+	/// assert_eq!(CMOSCenturyHandler::from_fadt_bytes(&fadt, 2019), CMOSCenturyHandler::CurrentYear(2019));
+	///
+	/// fadt[0x6C] = 0x32;
+	/// assert_eq!(CMOSCenturyHandler::from_fadt_bytes(&fadt, 2019), CMOSCenturyHandler::CenturyRegister(0x32));
+	/// assert_eq!(CMOSCenturyHandler::from_fadt_bytes(&fadt[..108], 2019), CMOSCenturyHandler::CurrentYear(2019));
 	/// ```
-	pub fn write_all(&mut self, input: &[u8; 128]) {
-		for i in 0..128 {
-			self.address_port.write(i);
-			self.data_port.write(input[i as usize]);
+	pub fn from_fadt_bytes(fadt: &[u8], current_year: usize) -> CMOSCenturyHandler {
+		match fadt.get(FADT_CENTURY_OFFSET) {
+			Some(&reg) if reg != 0 => CMOSCenturyHandler::CenturyRegister(reg),
+			_ => CMOSCenturyHandler::CurrentYear(current_year),
 		}
 	}
+}
 
-	/// Reads from a singe register in CMOS
-	/// # Examples
-	/// ```rust,no_run
-	/// # use cmos::{CMOS, CMOSCenturyHandler};
-	/// let mut cmos = unsafe { CMOS::new() };
-	/// // Read from register 0x04 in the CMOS
-	/// let reg_4 = cmos.read(0x04);
-	/// ```
-	pub fn read(&mut self, reg: u8) -> u8 {
-		self.address_port.write(reg);
-		self.data_port.read()
+/// Errors returned by the fallible CMOS and RTC operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CmosError {
+	/// Register B reads as `0xFF` or `0x00`, meaning there is no (powered) RTC to read from
+	RtcAbsent,
+	/// The century register number is out of the CMOS range
+	InvalidCenturyRegister(u8),
+	/// The current year given as a fallback is not plausible
+	InvalidCurrentYear(usize),
+	/// A date time field is out of range, or the year can't be represented by the RTC
+	InvalidField(RtcField),
+	/// The periodic interrupt rate is not between 3 and 15 (or 0 to disable it)
+	InvalidPeriodicRate(u8),
+	/// The date time read back from the RTC doesn't match the one written
+	WriteVerifyFailed,
+}
+
+/// Errors returned when parsing a date time string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+	/// The string is too short or too long for the expected layout, or is missing fields
+	Length,
+	/// The byte at this index should be a separator (`-`, `T`, `:` or `Z`) but isn't the expected one
+	Separator(usize),
+	/// The byte at this index is in a numeric field but isn't a decimal digit
+	NotADigit(usize),
+	/// A field is out of range
+	OutOfRange(RtcField),
+}
+
+impl Display for ParseError {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			ParseError::Length => write!(f, "unexpected length"),
+			ParseError::Separator(index) => write!(f, "unexpected separator at index {}", index),
+			ParseError::NotADigit(index) => write!(f, "unexpected non digit at index {}", index),
+			ParseError::OutOfRange(field) => write!(f, "{} out of range", field),
+		}
 	}
+}
 
-	/// Writes to a singe register in CMOS
-	/// # Examples
-	/// Writes `0x08` to register `0x04`
-	/// ```rust,no_run
-	/// # use cmos::{CMOS, CMOSCenturyHandler};
-	/// let mut cmos = unsafe { CMOS::new() };
-	/// // Write 0x08 into register 0x04
-	/// cmos.write(0x04, 0x08);
-	/// ```
-	pub fn write(&mut self, reg: u8, val: u8) {
-		self.address_port.write(reg);
-		self.data_port.write(val);
+impl Display for CmosError {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			CmosError::RtcAbsent => write!(f, "no RTC present"),
+			CmosError::InvalidCenturyRegister(reg) => write!(f, "invalid century register {:#04X}", reg),
+			CmosError::InvalidCurrentYear(year) => write!(f, "invalid current year {}", year),
+			CmosError::InvalidField(field) => write!(f, "invalid {}", field),
+			CmosError::InvalidPeriodicRate(rate) => write!(f, "invalid periodic interrupt rate {}", rate),
+			CmosError::WriteVerifyFailed => write!(f, "RTC write verification failed"),
+		}
 	}
+}
+
+/// How far a date time read from the RTC can be trusted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Confidence {
+	/// The date time is invalid, or the CMOS lost power and the RTC was reset
+	Low,
+	/// The date time is valid, but looks suspicious
+	Medium,
+	/// Every check passed
+	High,
+}
 
-	/// Reads and checks the status of the update in progress flag.
-	/// When reading from the RTC, it's best to read until this flag is 0.
+impl Confidence {
+	/// Grades a date time read from the RTC.
 	///
-	/// More info found [here](https://wiki.osdev.org/CMOS#RTC_Update_In_Progress)
+	/// * `Low` if the CMOS reports its RAM and time as invalid (`valid_time`, the VRT bit of register D, is clear)
+	///   or any field of the date time is out of range
+	/// * `Medium` if the values didn't stabilize quickly (over 2 extra reads, or `None` when the update in progress
+	///   flag never cleared), the year is not between 1980 and 2199, or the date time
+	///   [looks unset](struct.RTCDateTime.html#method.looks_unset)
+	/// * `High` otherwise
 	///
 	/// # Examples
-	/// ```rust,no_run
-	/// # use cmos::{CMOS, CMOSCenturyHandler};
-	/// let mut cmos = unsafe { CMOS::new() };
-	/// let mut reg0;
-	/// // Read register 0x00 until progress flag not 0
-	/// while cmos.get_update_in_progress_flag() != 0 {
-	///     reg0 = cmos.read(0x00);
-	/// }
+	/// ```rust
+	/// # use cmos::{Confidence, RTCDateTime};
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(Confidence::assess(&datetime, true, Some(1)), Confidence::High);
+	/// assert_eq!(Confidence::assess(&datetime, true, Some(5)), Confidence::Medium);
+	/// assert_eq!(Confidence::assess(&datetime, true, None), Confidence::Medium);
+	/// assert_eq!(Confidence::assess(&RTCDateTime { year: 2250, ..datetime }, true, Some(1)), Confidence::Medium);
+	/// let reset = RTCDateTime { year: 2000, month: 1, day: 1, hour: 0, minute: 3, second: 10 };
+	/// assert_eq!(Confidence::assess(&reset, true, Some(1)), Confidence::Medium);
+	/// assert_eq!(Confidence::assess(&datetime, false, Some(1)), Confidence::Low);
+	/// assert_eq!(Confidence::assess(&RTCDateTime { month: 13, ..datetime }, true, Some(1)), Confidence::Low);
 	/// ```
-	/// [`CMOS`]: struct.CMOS.html
-	pub fn get_update_in_progress_flag(&mut self) -> u8 { self.read(0x0A) & 0x80 }
-
-	fn read_into_rtc(&mut self, rtc_time: &mut RTCDateTime) {
-		while self.get_update_in_progress_flag() != 0 {
-			rtc_time.second = self.read(0x00);
-			rtc_time.minute = self.read(0x02);
-			rtc_time.hour = self.read(0x04);
-			rtc_time.day = self.read(0x07);
-			rtc_time.month = self.read(0x08);
-			rtc_time.year = self.read(0x09) as usize;
+	pub fn assess(datetime: &RTCDateTime, valid_time: bool, rereads: Option<u32>) -> Confidence {
+		if !valid_time || datetime.invalid_field().is_some() {
+			Confidence::Low
+		} else if rereads.unwrap_or(u32::MAX) > 2
+			|| !(1980..=2199).contains(&datetime.year)
+			|| datetime.looks_unset()
+		{
+			Confidence::Medium
+		} else {
+			Confidence::High
 		}
 	}
+}
 
-	/// Reads from the RTC part of CMOS
-	/// Returns an [`RTCDateTime`] struct, which includes all date time fields.
-	/// This method automatically converts BCD to binary values and 12 hours to 24 hour if necessary.
+/// Time scale the RTC is kept in, which depends on the OS that set it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeScale {
+	/// The RTC holds local time, like Windows keeps it by default
+	Local,
+	/// The RTC holds UTC
+	Utc,
+}
+
+/// Fields of a date time, used to report which one is invalid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RtcField {
+	Year,
+	Month,
+	Day,
+	Hour,
+	Minute,
+	Second,
+}
+
+impl Display for RtcField {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			RtcField::Year => write!(f, "year"),
+			RtcField::Month => write!(f, "month"),
+			RtcField::Day => write!(f, "day"),
+			RtcField::Hour => write!(f, "hour"),
+			RtcField::Minute => write!(f, "minute"),
+			RtcField::Second => write!(f, "second"),
+		}
+	}
+}
+
+/// Register offsets of the date time fields in the RTC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RtcRegisterMap {
+	/// Register of the seconds, `0x00` in the standard layout
+	pub second: u8,
+	/// Register of the minutes, `0x02` in the standard layout
+	pub minute: u8,
+	/// Register of the hours, `0x04` in the standard layout
+	pub hour: u8,
+	/// Register of the day of the month, `0x07` in the standard layout
+	pub day: u8,
+	/// Register of the month, `0x08` in the standard layout
+	pub month: u8,
+	/// Register of the year within the century, `0x09` in the standard layout
+	pub year: u8,
+	/// Register of the day of the week, `0x06` in the standard layout
+	pub weekday: u8,
+}
+
+impl RtcRegisterMap {
+	/// The standard layout, as documented [here](https://wiki.osdev.org/CMOS#Getting_Current_Date_and_Time_from_RTC)
+	pub const fn standard() -> RtcRegisterMap {
+		RtcRegisterMap { second: 0x00, minute: 0x02, hour: 0x04, day: 0x07, month: 0x08, year: 0x09, weekday: 0x06 }
+	}
+
+	/// The standard layout with the day and month registers swapped (day at `0x08`, month at `0x07`), as found on
+	/// some cloned chipsets
+	pub const fn swapped_day_month() -> RtcRegisterMap {
+		RtcRegisterMap { second: 0x00, minute: 0x02, hour: 0x04, day: 0x08, month: 0x07, year: 0x09, weekday: 0x06 }
+	}
+}
+
+impl Default for RtcRegisterMap {
+	fn default() -> RtcRegisterMap { RtcRegisterMap::standard() }
+}
+
+/// Data format of the RTC time registers, as configured in register B
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RtcFormat {
+	/// Values are stored in binary instead of BCD (bit 2 of register B)
+	pub binary: bool,
+	/// Hours are stored in 24 hour instead of 12 hour format (bit 1 of register B)
+	pub hour_24: bool,
+}
+
+impl RtcFormat {
+	/// Decodes the format bits of register B
 	///
 	/// # Examples
-	/// ```rust,no_run
-	/// # use cmos::{CMOS, CMOSCenturyHandler};
-	/// let mut cmos = unsafe { CMOS::new() };
-	/// // Get current RTC by current year of 2019
-	/// let rtc = cmos.read_rtc(CMOSCenturyHandler::CurrentYear(2019));
+	/// ```rust
+	/// # use cmos::RtcFormat;
+	/// // 24 hour BCD, the most common BIOS setting
+	/// assert_eq!(RtcFormat::from_register_b(0x02), RtcFormat { binary: false, hour_24: true });
 	/// ```
-	/// [`RTCDateTime`]: struct.RTCDateTime.html
-	pub fn read_rtc(&mut self, century_handler: CMOSCenturyHandler) -> RTCDateTime {
-		let mut rtc_time = RTCDateTime { second: 0, minute: 0, hour: 0, day: 0, month: 0, year: 0 };
+	pub fn from_register_b(register_b: u8) -> RtcFormat {
+		RtcFormat { binary: register_b & 0x04 != 0, hour_24: register_b & 0x02 != 0 }
+	}
 
-		// Note: This uses the "read registers until you get the same values twice in a row" technique to avoid getting
-		// dodgy/inconsistent values due to RTC updates
-		self.read_into_rtc(&mut rtc_time);
+	/// Returns the format bits to set in register B
+	pub fn register_b_bits(self) -> u8 { (if self.binary { 0x04 } else { 0 }) | (if self.hour_24 { 0x02 } else { 0 }) }
+}
 
-		let mut century = 0;
-		if let CMOSCenturyHandler::CenturyRegister(century_reg) = century_handler {
-			century = self.read(century_reg);
+/// Builds the whole configuration of the RTC, registers A and B, to write it in one go with [`apply`].
+///
+/// Starts from BCD and 24 hour format, every interrupt disabled and a periodic rate of 6 (1024Hz), which is what
+/// most BIOSes set up. The resulting registers are:
+/// * register A: the divider bits (4 to 6) are kept, and the rate selects the periodic interrupt frequency in the
+///   low 4 bits, `32768 >> (rate - 1)` Hz, from 3 (8192Hz) to 15 (2Hz), 0 meaning no periodic interrupt at all
+/// * register B: bit 6 enables the periodic interrupt, bit 5 the alarm interrupt, bit 4 the update ended interrupt,
+///   bit 2 selects binary and bit 1 the 24 hour format. The square wave (bit 3) and daylight saving (bit 0) bits
+///   are kept.
+///
+/// The time registers are not converted to the new format: set the time, for example with `CMOS::write_rtc`, after
+/// changing the format.
+///
+/// # Examples
+/// ```rust
+/// # use cmos::RtcConfigBuilder;
+/// let config = RtcConfigBuilder::new().binary(true).periodic_interrupt(true).periodic_rate(15);
+/// // Keep the 32.768kHz divider of register A
+/// assert_eq!(config.register_a(0x26), 0x2F);
+/// assert_eq!(config.register_b(0x02), 0x46);
+/// // Only the square wave and daylight saving bits of the current register B are kept
+/// assert_eq!(RtcConfigBuilder::new().hour_24(false).register_b(0xFF), 0x09);
+/// ```
+/// [`apply`]: struct.RtcConfigBuilder.html#method.apply
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RtcConfigBuilder {
+	format: RtcFormat,
+	periodic_interrupt: bool,
+	alarm_interrupt: bool,
+	update_interrupt: bool,
+	periodic_rate: u8,
+}
+
+impl RtcConfigBuilder {
+	/// Creates a builder with the usual BIOS configuration
+	pub fn new() -> RtcConfigBuilder {
+		RtcConfigBuilder {
+			format: RtcFormat { binary: false, hour_24: true },
+			periodic_interrupt: false,
+			alarm_interrupt: false,
+			update_interrupt: false,
+			periodic_rate: 6,
 		}
+	}
 
-		let mut last_second;
-		let mut last_minute;
-		let mut last_hour;
-		let mut last_day;
-		let mut last_month;
-		let mut last_year;
-		let mut last_century;
-
-		loop {
-			last_second = rtc_time.second;
-			last_minute = rtc_time.minute;
-			last_hour = rtc_time.hour;
-			last_day = rtc_time.day;
-			last_month = rtc_time.month;
-			last_year = rtc_time.year;
-			last_century = century;
-
-			self.read_into_rtc(&mut rtc_time);
-
-			if last_second != rtc_time.second
-				|| last_minute != rtc_time.minute
-				|| last_hour != rtc_time.hour
-				|| last_day != rtc_time.day
-				|| last_month != rtc_time.month
-				|| last_year != rtc_time.year
-				|| last_century != century
-			{
-				break;
-			}
+	/// Stores the time registers in binary instead of BCD
+	pub fn binary(mut self, binary: bool) -> RtcConfigBuilder {
+		self.format.binary = binary;
+		self
+	}
+
+	/// Stores the hours in 24 hour instead of 12 hour format
+	pub fn hour_24(mut self, hour_24: bool) -> RtcConfigBuilder {
+		self.format.hour_24 = hour_24;
+		self
+	}
+
+	/// Enables or disables the periodic interrupt
+	pub fn periodic_interrupt(mut self, enabled: bool) -> RtcConfigBuilder {
+		self.periodic_interrupt = enabled;
+		self
+	}
+
+	/// Enables or disables the alarm interrupt
+	pub fn alarm_interrupt(mut self, enabled: bool) -> RtcConfigBuilder {
+		self.alarm_interrupt = enabled;
+		self
+	}
+
+	/// Enables or disables the update ended interrupt
+	pub fn update_interrupt(mut self, enabled: bool) -> RtcConfigBuilder {
+		self.update_interrupt = enabled;
+		self
+	}
+
+	/// Sets the periodic interrupt rate, from 3 (8192Hz) to 15 (2Hz), or 0 to stop the periodic interrupt.
+	/// Other rates are rejected by [`apply`].
+	///
+	/// [`apply`]: struct.RtcConfigBuilder.html#method.apply
+	pub fn periodic_rate(mut self, rate: u8) -> RtcConfigBuilder {
+		self.periodic_rate = rate;
+		self
+	}
+
+	/// Returns the value of register A for this configuration, given its current value
+	pub fn register_a(&self, current: u8) -> u8 { (current & 0x70) | (self.periodic_rate & 0x0F) }
+
+	/// Returns the value of register B for this configuration, given its current value.
+	/// The SET bit (bit 7) is clear, so the RTC runs.
+	pub fn register_b(&self, current: u8) -> u8 {
+		let interrupts = (if self.periodic_interrupt { 0x40 } else { 0 })
+			| (if self.alarm_interrupt { 0x20 } else { 0 })
+			| (if self.update_interrupt { 0x10 } else { 0 });
+		(current & 0x09) | interrupts | self.format.register_b_bits()
+	}
+
+	/// Writes the configuration into registers A and B, with RTC updates halted (through bit 7 of register B) while
+	/// they change.
+	/// Returns [`CmosError::InvalidPeriodicRate`] without writing anything if the periodic rate is out of range.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{CmosError, MockPorts, RtcConfigBuilder, CMOS};
+	/// let mut registers = [0; 128];
+	/// registers[0x0A] = 0x26;
+	/// // BCD, 24 hour format, square wave enabled
+	/// registers[0x0B] = 0x0A;
+	/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+	/// RtcConfigBuilder::new().binary(true).periodic_interrupt(true).periodic_rate(15).apply(&mut cmos).unwrap();
+	/// assert_eq!(cmos.ports().registers[0x0A], 0x2F);
+	/// assert_eq!(cmos.ports().registers[0x0B], 0x4E);
+	///
+	/// // Rate 2 is out of range, and nothing is written
+	/// let result = RtcConfigBuilder::new().update_interrupt(true).periodic_rate(2).apply(&mut cmos);
+	/// assert_eq!(result, Err(CmosError::InvalidPeriodicRate(2)));
+	/// assert_eq!((cmos.ports().registers[0x0A], cmos.ports().registers[0x0B]), (0x2F, 0x4E));
+	/// ```
+	/// [`CmosError::InvalidPeriodicRate`]: enum.CmosError.html#variant.InvalidPeriodicRate
+	#[cfg(all(any(feature = "x86", feature = "x86_64-port"), not(feature = "read-only")))]
+	pub fn apply<P: PortIO>(&self, cmos: &mut CMOS<P>) -> Result<(), CmosError> {
+		match self.periodic_rate {
+			0 | 3..=15 => {},
+			rate => return Err(CmosError::InvalidPeriodicRate(rate)),
 		}
+		let register_b = cmos.read(0x0B);
+		cmos.write(0x0B, register_b | 0x80);
+		let register_a = cmos.read(0x0A);
+		cmos.write(0x0A, self.register_a(register_a));
+		cmos.write(0x0B, self.register_b(register_b));
+		Ok(())
+	}
+}
+
+impl Default for RtcConfigBuilder {
+	fn default() -> RtcConfigBuilder { RtcConfigBuilder::new() }
+}
+
+/// Raw RTC time registers, as stored in the hardware (BCD or binary, 12 or 24 hour, depending on register B)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RawRtcRegisters {
+	pub second: u8,
+	pub minute: u8,
+	pub hour: u8,
+	pub weekday: u8,
+	pub day: u8,
+	pub month: u8,
+	pub year: u8,
+	pub register_b: u8,
+	/// The raw century register, only used with `CMOSCenturyHandler::CenturyRegister`
+	pub century: u8,
+}
 
-		let register_b = self.read(0x0B);
+impl RawRtcRegisters {
+	/// Converts the raw registers into an [`RTCDateTime`], from BCD to binary and from 12 hour to 24 hour format
+	/// according to register B, and computes the full year with the century handler.
+	/// Only the register number of `CenturyRegister` is ignored: the century comes from the `century` field.
+	/// The weekday register isn't used, and the result isn't validated, except for a second of 60: the RTC doesn't
+	/// model leap seconds, but some hardware reads 60 for a moment during a leap second adjustment, which is clamped
+	/// to 59 instead of giving an invalid date time. [`is_leap_second`] tells when that happened.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{CMOSCenturyHandler, RawRtcRegisters, RTCDateTime};
+	/// // BCD, 24 hour format
+	/// let raw = RawRtcRegisters {
+	///     second: 0x05,
+	///     minute: 0x04,
+	///     hour: 0x15,
+	///     weekday: 4,
+	///     day: 0x02,
+	///     month: 0x01,
+	///     year: 0x19,
+	///     register_b: 0x02,
+	///     century: 0x20,
+	/// };
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 15, minute: 4, second: 5 };
+	/// assert_eq!(raw.convert(CMOSCenturyHandler::CenturyRegister(0x32)), datetime);
+	/// assert_eq!(raw.convert(CMOSCenturyHandler::CurrentYear(2019)), datetime);
+	///
+	/// // 12 hour format, where 12 AM is midnight and 12 PM is noon
+	/// let hour_12 = |hour| RawRtcRegisters { hour, register_b: 0x00, ..raw }.convert(CMOSCenturyHandler::CurrentYear(2019));
+	/// assert_eq!(hour_12(0x12).hour, 0);
+	/// assert_eq!(hour_12(0x92).hour, 12);
+	/// assert_eq!(hour_12(0x83).hour, 15);
+	///
+	/// // A leap second read as 23:59:60
+	/// let raw = RawRtcRegisters { second: 0x60, minute: 0x59, hour: 0x23, ..raw };
+	/// assert_eq!(raw.convert(CMOSCenturyHandler::CurrentYear(2019)).as_tuple(), (2019, 1, 2, 23, 59, 59));
+	/// ```
+	/// [`RTCDateTime`]: struct.RTCDateTime.html
+	/// [`is_leap_second`]: struct.RawRtcRegisters.html#method.is_leap_second
+	pub fn convert(&self, century_handler: CMOSCenturyHandler) -> RTCDateTime {
+		let mut rtc_time = RTCDateTime {
+			year: self.year as usize,
+			month: self.month,
+			day: self.day,
+			hour: self.hour,
+			minute: self.minute,
+			second: self.second,
+		};
+		let mut century = self.century;
 
 		// Convert BCD to binary values if necessary
-		if (register_b & 0x04) == 0 {
-			rtc_time.second = (rtc_time.second & 0x0F) + ((rtc_time.second / 16) * 10);
-			rtc_time.minute = (rtc_time.minute & 0x0F) + ((rtc_time.minute / 16) * 10);
-			rtc_time.hour = ((rtc_time.hour & 0x0F) + (((rtc_time.hour & 0x70) / 16) * 10)) | (rtc_time.hour & 0x80);
-			rtc_time.day = (rtc_time.day & 0x0F) + ((rtc_time.day / 16) * 10);
-			rtc_time.month = (rtc_time.month & 0x0F) + ((rtc_time.month / 16) * 10);
-			rtc_time.year = (rtc_time.year & 0x0F) + ((rtc_time.year / 16) * 10);
+		if (self.register_b & 0x04) == 0 {
+			rtc_time.second = bcd_to_binary(rtc_time.second);
+			rtc_time.minute = bcd_to_binary(rtc_time.minute);
+			rtc_time.hour = bcd_to_binary(rtc_time.hour & 0x7F) | (rtc_time.hour & 0x80);
+			rtc_time.day = bcd_to_binary(rtc_time.day);
+			rtc_time.month = bcd_to_binary(rtc_time.month);
+			rtc_time.year = bcd_to_binary(self.year) as usize;
 
 			if let CMOSCenturyHandler::CenturyRegister(_) = century_handler {
-				century = (century & 0x0F) + ((century / 16) * 10);
+				century = bcd_to_binary(century);
 			}
 		}
 
-		// Convert 12 hour clock to 24 hour clock if necessary
-		if ((register_b & 0x02) == 0) && ((rtc_time.hour & 0x80) != 0) {
-			rtc_time.hour = ((rtc_time.hour & 0x7F) + 12) % 24;
+		// Convert 12 hour clock to 24 hour clock if necessary: 12 AM is midnight, 12 PM is noon
+		if (self.register_b & 0x02) == 0 {
+			let pm = if (rtc_time.hour & 0x80) != 0 { 12 } else { 0 };
+			rtc_time.hour = (rtc_time.hour & 0x7F) % 12 + pm;
 		}
 
-		// Calculate the full (4-digit) year
-		match century_handler {
-			CMOSCenturyHandler::CenturyRegister(_) => rtc_time.year += (century * 100) as usize,
-			CMOSCenturyHandler::CurrentYear(current_year) => {
-				rtc_time.year += (current_year / 100) * 100;
-				if rtc_time.year < current_year {
-					rtc_time.year += 100;
-				}
-			}
+		// Clamp a leap second, which the date time can't represent
+		if rtc_time.second == 60 {
+			rtc_time.second = 59;
 		}
 
+		// Calculate the full (4-digit) year
+		rtc_time.year = century_handler.full_year(rtc_time.year as u8, century);
+
 		rtc_time
 	}
+
+	/// Returns whether the seconds register reads 60, in the format of register B: a leap second, which
+	/// [`convert`] clamps to 59
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RawRtcRegisters;
+	/// assert!(RawRtcRegisters { second: 0x60, register_b: 0x02, ..Default::default() }.is_leap_second());
+	/// assert!(RawRtcRegisters { second: 60, register_b: 0x06, ..Default::default() }.is_leap_second());
+	/// assert!(!RawRtcRegisters { second: 0x59, register_b: 0x02, ..Default::default() }.is_leap_second());
+	/// // 0x60 is 96 in binary
+	/// assert!(!RawRtcRegisters { second: 0x60, register_b: 0x06, ..Default::default() }.is_leap_second());
+	/// ```
+	/// [`convert`]: struct.RawRtcRegisters.html#method.convert
+	pub fn is_leap_second(&self) -> bool {
+		let second = if (self.register_b & 0x04) == 0 { bcd_to_binary(self.second) } else { self.second };
+		second == 60
+	}
 }
 
-/// Enum for determining how to calculate the year when reading the RTC
-#[derive(Debug, Clone, Copy)]
-pub enum CMOSCenturyHandler {
-	/// This option is for providing the number of the century register in the RTC
-	CenturyRegister(u8),
-	/// This option is for providing the current year as a backup
-	CurrentYear(usize),
+/// Primary display adapter, as recorded in the equipment byte
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayType {
+	/// An adapter with its own BIOS, like EGA or VGA
+	Ega,
+	/// CGA, in 40x25 mode
+	Cga40,
+	/// CGA, in 80x25 mode
+	Cga80,
+	/// Monochrome (MDA)
+	Monochrome,
 }
 
-/// Results struct from reading RTC with self-explanatory fields
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
-pub struct RTCDateTime {
-	pub year: usize,
-	pub month: u8,
-	pub day: u8,
-	pub hour: u8,
-	pub minute: u8,
-	pub second: u8,
+/// The legacy equipment byte (register `0x14`), decoded
+///
+/// The AT layout of the byte has no base memory bit: bits 3-2, which held the planar RAM size in the PC/XT BIOS
+/// equipment word, are the display and keyboard enabled flags here. The base memory size is in registers `0x15`
+/// (low byte) and `0x16` (high byte), in KiB.
+///
+/// # Examples
+/// ```rust
+/// # use cmos::{DisplayType, EquipmentInfo};
+/// // 2 floppy drives, 80x25 CGA, display and keyboard enabled, math coprocessor
+/// let info = EquipmentInfo::from_byte(0b0110_1111);
+/// assert_eq!(
+///     info,
+///     EquipmentInfo {
+///         floppy_drives: 2,
+///         display: DisplayType::Cga80,
+///         display_enabled: true,
+///         keyboard_enabled: true,
+///         math_coprocessor: true,
+///     }
+/// );
+/// // The drive count is ignored when bit 0 says no floppy drive is installed
+/// assert_eq!(EquipmentInfo::from_byte(0b1100_0000).floppy_drives, 0);
+///
+/// // Bits 3-2 are the enabled flags, not a base memory size
+/// let info = EquipmentInfo::from_byte(0b0000_0100);
+/// assert_eq!((info.display_enabled, info.keyboard_enabled), (false, true));
+/// let info = EquipmentInfo::from_byte(0b0000_1000);
+/// assert_eq!((info.display_enabled, info.keyboard_enabled), (true, false));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EquipmentInfo {
+	/// Number of floppy drives, from 0 to 4
+	pub floppy_drives: u8,
+	/// The primary display adapter
+	pub display: DisplayType,
+	/// Whether the display is enabled, cleared to boot without one
+	pub display_enabled: bool,
+	/// Whether the keyboard is enabled, cleared to boot without one
+	pub keyboard_enabled: bool,
+	/// Whether a math coprocessor is installed
+	pub math_coprocessor: bool,
 }
 
-pub const MAX: RTCDateTime = RTCDateTime { year: usize::MAX, month: 12, day: 31, hour: 23, minute: 59, second: 59 };
-
-pub const MIN: RTCDateTime = RTCDateTime { year: 0, month: 1, day: 1, hour: 0, minute: 0, second: 0 };
-
-impl Ord for RTCDateTime {
-	/// Compare the fields one by one in descending order
-	fn cmp(&self, other: &Self) -> Ordering {
-		(self.year, self.month, self.day, self.hour, self.minute, self.second).cmp(&(
-			other.year,
-			other.month,
-			other.day,
-			other.hour,
-			other.minute,
-			other.second,
-		))
+impl EquipmentInfo {
+	/// Decodes the equipment byte:
+	///
+	/// * bits 7-6: number of floppy drives minus one, if bit 0 is set
+	/// * bits 5-4: display type, 0 for EGA/VGA, 1 for CGA 40x25, 2 for CGA 80x25 and 3 for monochrome
+	/// * bit 3: display enabled
+	/// * bit 2: keyboard enabled
+	/// * bit 1: math coprocessor installed
+	/// * bit 0: floppy drives installed
+	///
+	/// There is no base memory bit, see [`EquipmentInfo`].
+	/// [`EquipmentInfo`]: struct.EquipmentInfo.html
+	pub fn from_byte(byte: u8) -> EquipmentInfo {
+		let display = match (byte >> 4) & 0x03 {
+			0 => DisplayType::Ega,
+			1 => DisplayType::Cga40,
+			2 => DisplayType::Cga80,
+			_ => DisplayType::Monochrome,
+		};
+		EquipmentInfo {
+			floppy_drives: if (byte & 0x01) != 0 { (byte >> 6) + 1 } else { 0 },
+			display,
+			display_enabled: (byte & 0x08) != 0,
+			keyboard_enabled: (byte & 0x04) != 0,
+			math_coprocessor: (byte & 0x02) != 0,
+		}
 	}
 }
 
-impl PartialOrd for RTCDateTime {
-	fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+/// Shutdown status code (register `0x0F`), telling the BIOS what to do after the next CPU reset.
+///
+/// The 286 can only leave protected mode through a reset, so software returning to real mode sets the code, stores
+/// the address to resume at in the reset vector (`40h:0067h`), and resets the CPU, through the keyboard controller
+/// or a triple fault. The BIOS then checks the code and, instead of a full POST, resumes the program. 386 and later
+/// CPUs can leave protected mode directly, but the mechanism is still used to reset the CPU, like to start the
+/// application processors of some multiprocessor systems.
+///
+/// # Examples
+/// ```rust
+/// # use cmos::ShutdownStatus;
+/// assert_eq!(ShutdownStatus::from(0x00), ShutdownStatus::Normal);
+/// assert_eq!(ShutdownStatus::from(0x05), ShutdownStatus::JumpWithEoi);
+/// assert_eq!(ShutdownStatus::from(0x0A), ShutdownStatus::JumpWithoutEoi);
+/// assert_eq!(ShutdownStatus::from(0x42), ShutdownStatus::Other(0x42));
+/// for code in 0..=255 {
+///     assert_eq!(u8::from(ShutdownStatus::from(code)), code);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownStatus {
+	/// `0x00`: normal reset, with a full POST
+	Normal,
+	/// `0x04`: boot the OS (`INT 19h`)
+	Boot,
+	/// `0x05`: flush the keyboard buffer, send an EOI to the interrupt controller, and jump to the reset vector
+	JumpWithEoi,
+	/// `0x09`: return from a block move (`INT 15h`, `AH = 87h`)
+	BlockMove,
+	/// `0x0A`: jump to the reset vector, without an EOI
+	JumpWithoutEoi,
+	/// `0x0B`: `IRET` through the reset vector
+	IretToResetVector,
+	/// `0x0C`: `RETF` through the reset vector
+	RetfToResetVector,
+	/// Any other code, most of them being used by the POST itself
+	Other(u8),
 }
 
-impl Display for RTCDateTime {
-	/// Prints a `RTCDateTime` formatted according to the [ISO 8601](https://en.wikipedia.org/wiki/ISO_8601) standard.
-	fn fmt(&self, f: &mut Formatter) -> Result {
-		write!(f, "{}-{}-{}T{}:{}:{}Z", self.year, self.month, self.day, self.hour, self.minute, self.second)
+impl From<u8> for ShutdownStatus {
+	fn from(code: u8) -> ShutdownStatus {
+		match code {
+			0x00 => ShutdownStatus::Normal,
+			0x04 => ShutdownStatus::Boot,
+			0x05 => ShutdownStatus::JumpWithEoi,
+			0x09 => ShutdownStatus::BlockMove,
+			0x0A => ShutdownStatus::JumpWithoutEoi,
+			0x0B => ShutdownStatus::IretToResetVector,
+			0x0C => ShutdownStatus::RetfToResetVector,
+			code => ShutdownStatus::Other(code),
+		}
 	}
 }
 
-impl RTCDateTime {
-	/// Check if the `RTCDateTime` instance is a valid date.
-	/// The function takes into account the number of days in months and leap years.
-	#[inline]
-	pub fn is_valid(&self) -> bool {
-		self.month < 13
-			&& self.hour < 24
-			&& self.minute < 60
-			&& self.second < 60
-			&& self.day < RTCDateTime::days_by_month(self.year, self.month)
-	}
-
-	/// Attempt to create a valid `RTCDateTime` from a tuple.
-	/// Returns `Some(RTCDateTime)` in case of success, or `None` if the operation failed.
-	/// The datetime eventually returned is guaranteed to be valid.
-	#[inline]
-	pub fn from_tuple(tuple: &(usize, u8, u8, u8, u8, u8)) -> Option<Self> {
-		let datetime = Self { year: tuple.0, month: tuple.1, day: tuple.2, hour: tuple.3, minute: tuple.4, second: tuple.5 };
-		if datetime.is_valid() {
-			Some(datetime)
-		} else {
-			None
+impl From<ShutdownStatus> for u8 {
+	fn from(status: ShutdownStatus) -> u8 {
+		match status {
+			ShutdownStatus::Normal => 0x00,
+			ShutdownStatus::Boot => 0x04,
+			ShutdownStatus::JumpWithEoi => 0x05,
+			ShutdownStatus::BlockMove => 0x09,
+			ShutdownStatus::JumpWithoutEoi => 0x0A,
+			ShutdownStatus::IretToResetVector => 0x0B,
+			ShutdownStatus::RetfToResetVector => 0x0C,
+			ShutdownStatus::Other(code) => code,
 		}
 	}
+}
 
-	/// Returns a tuple containing the fields of a `RTCDateTime` by descending order.
-	#[inline]
-	pub fn as_tuple(&self) -> (usize, u8, u8, u8, u8, u8) {
-		(self.year, self.month, self.day, self.hour, self.minute, self.second)
+/// Converts a batch of raw RTC reads, like a log of captured registers, with [`RawRtcRegisters::convert`].
+/// Each read is decoded in the format of its own register B. The reads are converted into `out` in order, up to the
+/// length of the shorter slice.
+///
+/// # Examples
+/// ```rust
+/// # use cmos::{convert_batch, CMOSCenturyHandler, RawRtcRegisters, RTCDateTime};
+/// let date = RawRtcRegisters { day: 2, month: 1, year: 0x19, ..Default::default() };
+/// let raws = [
+///     // BCD, 24 hour format
+///     RawRtcRegisters { second: 0x05, minute: 0x04, hour: 0x15, register_b: 0x02, ..date },
+///     // Binary, 12 hour format (3 PM)
+///     RawRtcRegisters { second: 6, minute: 4, hour: 0x80 | 3, year: 19, register_b: 0x04, ..date },
+/// ];
+/// let mut out = [RTCDateTime::default(); 2];
+/// convert_batch(&raws, CMOSCenturyHandler::CurrentYear(2019), &mut out);
+/// assert_eq!(out[0], RTCDateTime { year: 2019, month: 1, day: 2, hour: 15, minute: 4, second: 5 });
+/// assert_eq!(out[1], RTCDateTime { year: 2019, month: 1, day: 2, hour: 15, minute: 4, second: 6 });
+/// ```
+/// [`RawRtcRegisters::convert`]: struct.RawRtcRegisters.html#method.convert
+pub fn convert_batch(raws: &[RawRtcRegisters], century_handler: CMOSCenturyHandler, out: &mut [RTCDateTime]) {
+	for (raw, datetime) in raws.iter().zip(out.iter_mut()) {
+		*datetime = raw.convert(century_handler);
 	}
+}
 
-	/// Returns the maximal number of days given a month and a year.
-	#[doc(hidden)]
-	fn days_by_month(year: usize, month: u8) -> u8 {
-		match month {
-			1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
-			4 | 6 | 9 | 11 => 30,
-			2 => {
-				if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 {
-					29
-				} else {
-					28
-				}
-			},
-			_ => 0,
-		}
+/// Returns the frequency, in Hz, of the periodic interrupt for a rate of register A: `32768 >> (rate - 1)` from 3
+/// (8192Hz) to 15 (2Hz). A rate of 0 disables the interrupt and gives 0. Rates 1 and 2 don't follow the formula but
+/// repeat rates 8 and 9 (256Hz and 128Hz) on the MC146818, which they are usually avoided for. Only the low 4 bits of
+/// `rate` are used, like in register A.
+///
+/// # Examples
+/// ```rust
+/// # use cmos::periodic_rate_to_hz;
+/// let table = [0, 256, 128, 8192, 4096, 2048, 1024, 512, 256, 128, 64, 32, 16, 8, 4, 2];
+/// for (rate, &hz) in table.iter().enumerate() {
+///     assert_eq!(periodic_rate_to_hz(rate as u8), hz);
+/// }
+/// ```
+pub fn periodic_rate_to_hz(rate: u8) -> u32 {
+	match rate & 0x0F {
+		0 => 0,
+		1 => 256,
+		2 => 128,
+		rate => 32768 >> (rate - 1),
 	}
 }
+
+/// Converts a BCD encoded byte into binary
+pub(crate) fn bcd_to_binary(value: u8) -> u8 { (value & 0x0F) + ((value / 16) * 10) }
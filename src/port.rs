@@ -0,0 +1,140 @@
+//! Port I/O backends used by [`CMOS`](../struct.CMOS.html).
+//!
+//! `cpuio` is used by default. With the `x86_64-port` feature, the `x86_64` crate is used instead, which builds with
+//! current compilers (`cpuio` relies on `llvm_asm!`, which was removed from nightly).
+//! [`MockPorts`](../struct.MockPorts.html) stands in for the hardware in tests.
+
+use core::fmt::{self, Debug, Formatter};
+
+/// The CMOS address port, selecting the register to access
+pub(crate) const ADDRESS_PORT: u16 = 0x70;
+/// The CMOS data port, reading and writing the selected register
+pub(crate) const DATA_PORT: u16 = 0x71;
+
+/// Port I/O as used by [`CMOS`], which only goes through this trait to access the CMOS.
+/// Implementing it lets the CMOS logic run against something else than the hardware, like [`MockPorts`].
+///
+/// [`CMOS`]: struct.CMOS.html
+/// [`MockPorts`]: struct.MockPorts.html
+pub trait PortIO {
+	/// Reads a byte from the I/O port at `port`
+	fn read(&mut self, port: u16) -> u8;
+	/// Writes a byte to the I/O port at `port`
+	fn write(&mut self, port: u16, val: u8);
+}
+
+/// The I/O ports of the machine, the default [`PortIO`] of [`CMOS`]
+///
+/// [`PortIO`]: trait.PortIO.html
+/// [`CMOS`]: struct.CMOS.html
+#[derive(Debug)]
+pub struct HardwarePorts {
+	_private: (),
+}
+
+impl HardwarePorts {
+	/// Creates the ports
+	///
+	/// # Safety
+	/// The caller must be allowed to do port I/O (running in ring 0, or with a high enough IOPL), and must ensure
+	/// nothing else accesses the CMOS ports at the same time: the address and data ports are a stateful pair, so an
+	/// access interleaved with another one reads or writes the wrong register.
+	pub unsafe fn new() -> HardwarePorts { HardwarePorts { _private: () } }
+}
+
+impl PortIO for HardwarePorts {
+	// Safety: the ports were handed out by the unsafe `HardwarePorts::new`, whose caller vouched for them
+	fn read(&mut self, port: u16) -> u8 { unsafe { Port::new(port) }.read() }
+
+	// Safety: the ports were handed out by the unsafe `HardwarePorts::new`, whose caller vouched for them
+	fn write(&mut self, port: u16, val: u8) { unsafe { Port::new(port) }.write(val) }
+}
+
+/// A [`PortIO`] serving the 128 CMOS registers from an array, to use [`CMOS`] without hardware, in tests.
+///
+/// Writing a register number to the address port (`0x70`) selects it, ignoring the NMI disable bit (bit 7), and the
+/// data port (`0x71`) then reads and writes the selected register. Other ports read as `0xFF` and ignore writes.
+///
+/// # Examples
+/// ```rust
+/// # use cmos::{MockPorts, CMOS};
+/// let mut registers = [0; 128];
+/// registers[0x04] = 0x08;
+/// let mut cmos = CMOS::with_ports(MockPorts::new(registers));
+/// assert_eq!(cmos.read(0x04), 0x08);
+/// cmos.ports_mut().registers[0x05] = 0x30;
+/// assert_eq!(cmos.read(0x05), 0x30);
+/// ```
+/// [`PortIO`]: trait.PortIO.html
+/// [`CMOS`]: struct.CMOS.html
+#[derive(Clone)]
+pub struct MockPorts {
+	/// The CMOS registers
+	pub registers: [u8; 128],
+	address: u8,
+}
+
+impl MockPorts {
+	/// Creates the ports, serving the given registers
+	pub fn new(registers: [u8; 128]) -> MockPorts { MockPorts { registers, address: 0 } }
+}
+
+impl PortIO for MockPorts {
+	fn read(&mut self, port: u16) -> u8 {
+		match port {
+			DATA_PORT => self.registers[usize::from(self.address)],
+			_ => 0xFF,
+		}
+	}
+
+	fn write(&mut self, port: u16, val: u8) {
+		match port {
+			ADDRESS_PORT => self.address = val & 0x7F,
+			DATA_PORT => self.registers[usize::from(self.address)] = val,
+			_ => {},
+		}
+	}
+}
+
+impl Debug for MockPorts {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.debug_struct("MockPorts").field("registers", &&self.registers[..]).field("address", &self.address).finish()
+	}
+}
+
+/// An 8 bit I/O port
+pub(crate) struct Port {
+	address: u16,
+	#[cfg(not(feature = "x86_64-port"))]
+	inner: cpuio::Port<u8>,
+	#[cfg(feature = "x86_64-port")]
+	inner: x86_64::instructions::port::Port<u8>,
+}
+
+impl Port {
+	/// Note: This function is unsafe due to the creation of port I/O
+	#[cfg(not(feature = "x86_64-port"))]
+	pub(crate) unsafe fn new(address: u16) -> Port { Port { address, inner: cpuio::Port::new(address) } }
+
+	/// Note: This function is unsafe due to the creation of port I/O
+	#[cfg(feature = "x86_64-port")]
+	pub(crate) unsafe fn new(address: u16) -> Port { Port { address, inner: x86_64::instructions::port::Port::new(address) } }
+
+	#[cfg(not(feature = "x86_64-port"))]
+	pub(crate) fn read(&mut self) -> u8 { self.inner.read() }
+
+	// Safety: the port was handed out by the unsafe `Port::new`, whose caller vouched for it
+	#[cfg(feature = "x86_64-port")]
+	pub(crate) fn read(&mut self) -> u8 { unsafe { self.inner.read() } }
+
+	#[cfg(not(feature = "x86_64-port"))]
+	pub(crate) fn write(&mut self, value: u8) { self.inner.write(value) }
+
+	// Safety: the port was handed out by the unsafe `Port::new`, whose caller vouched for it
+	#[cfg(feature = "x86_64-port")]
+	pub(crate) fn write(&mut self, value: u8) { unsafe { self.inner.write(value) } }
+}
+
+impl Debug for Port {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result { f.debug_struct("Port").field("address", &self.address).finish() }
+}
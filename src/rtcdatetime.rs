@@ -0,0 +1,2231 @@
+use crate::{buf::SliceWriter, CMOSCenturyHandler, CmosError, ParseError, RawRtcRegisters, RtcField, RtcFormat};
+use core::{
+	cmp::Ordering,
+	convert::TryFrom,
+	fmt::{self, Display, Formatter, Write},
+	ops::{Add, AddAssign, Sub, SubAssign},
+};
+
+/// Results struct from reading RTC with self-explanatory fields
+///
+/// With the `serde` feature, it can be serialized and deserialized with serde, as a struct of its fields.
+///
+/// # Examples
+/// ```rust
+/// # #[cfg(feature = "serde")] {
+/// # use cmos::RTCDateTime;
+/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+/// let json = serde_json::to_string(&datetime).unwrap();
+/// assert_eq!(json, r#"{"year":2019,"month":1,"day":2,"hour":3,"minute":4,"second":5}"#);
+/// assert_eq!(serde_json::from_str::<RTCDateTime>(&json).unwrap(), datetime);
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RTCDateTime {
+	pub year: usize,
+	pub month: u8,
+	pub day: u8,
+	pub hour: u8,
+	pub minute: u8,
+	pub second: u8,
+}
+
+pub const MAX: RTCDateTime = RTCDateTime { year: usize::MAX, month: 12, day: 31, hour: 23, minute: 59, second: 59 };
+
+pub const MIN: RTCDateTime = RTCDateTime { year: 0, month: 1, day: 1, hour: 0, minute: 0, second: 0 };
+
+/// Years commonly used as defaults by BIOSes after the RTC was reset, used by [`RTCDateTime::looks_unset`]
+///
+/// [`RTCDateTime::looks_unset`]: struct.RTCDateTime.html#method.looks_unset
+pub const UNSET_YEARS: [usize; 3] = [1970, 1980, 2000];
+
+/// Days of the week
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weekday {
+	Sunday,
+	Monday,
+	Tuesday,
+	Wednesday,
+	Thursday,
+	Friday,
+	Saturday,
+}
+
+/// Months of the year, numbered from 1 like the RTC month register
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Month {
+	January = 1,
+	February = 2,
+	March = 3,
+	April = 4,
+	May = 5,
+	June = 6,
+	July = 7,
+	August = 8,
+	September = 9,
+	October = 10,
+	November = 11,
+	December = 12,
+}
+
+impl Month {
+	/// Converts a month number (1 to 12) into a `Month`.
+	/// Returns `None` if the number is out of range.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::Month;
+	/// assert_eq!(Month::from_u8(2), Some(Month::February));
+	/// assert_eq!(Month::from_u8(13), None);
+	/// assert_eq!(Month::from_u8(12).map(Month::as_u8), Some(12));
+	/// ```
+	pub fn from_u8(month: u8) -> Option<Month> {
+		match month {
+			1 => Some(Month::January),
+			2 => Some(Month::February),
+			3 => Some(Month::March),
+			4 => Some(Month::April),
+			5 => Some(Month::May),
+			6 => Some(Month::June),
+			7 => Some(Month::July),
+			8 => Some(Month::August),
+			9 => Some(Month::September),
+			10 => Some(Month::October),
+			11 => Some(Month::November),
+			12 => Some(Month::December),
+			_ => None,
+		}
+	}
+
+	/// Returns the month number (1 to 12).
+	pub fn as_u8(self) -> u8 { self as u8 }
+
+	/// Returns the English name of the month.
+	pub fn name(self) -> &'static str {
+		match self {
+			Month::January => "January",
+			Month::February => "February",
+			Month::March => "March",
+			Month::April => "April",
+			Month::May => "May",
+			Month::June => "June",
+			Month::July => "July",
+			Month::August => "August",
+			Month::September => "September",
+			Month::October => "October",
+			Month::November => "November",
+			Month::December => "December",
+		}
+	}
+
+	/// Returns the number of days in the month for the given year.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::Month;
+	/// assert_eq!(Month::February.days(2019), 28);
+	/// assert_eq!(Month::February.days(2020), 29);
+	/// ```
+	pub fn days(self, year: usize) -> u8 { RTCDateTime::days_by_month(year, self.as_u8()) }
+
+	/// Returns the following month, wrapping from December to January.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::Month;
+	/// assert_eq!(Month::December.next(), Month::January);
+	/// assert_eq!(Month::January.prev(), Month::December);
+	/// assert_eq!(Month::June.next().prev(), Month::June);
+	/// ```
+	pub fn next(self) -> Month { Month::from_u8(self.as_u8() % 12 + 1).unwrap_or(Month::January) }
+
+	/// Returns the preceding month, wrapping from January to December.
+	pub fn prev(self) -> Month { Month::from_u8((self.as_u8() + 10) % 12 + 1).unwrap_or(Month::December) }
+}
+
+/// Seasons of the year
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Season {
+	Spring,
+	Summer,
+	Autumn,
+	Winter,
+}
+
+/// Hemispheres of the Earth, which have opposite seasons
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hemisphere {
+	Northern,
+	Southern,
+}
+
+impl Ord for RTCDateTime {
+	/// Compare the fields one by one in descending order
+	fn cmp(&self, other: &Self) -> Ordering {
+		(self.year, self.month, self.day, self.hour, self.minute, self.second).cmp(&(
+			other.year,
+			other.month,
+			other.day,
+			other.hour,
+			other.minute,
+			other.second,
+		))
+	}
+}
+
+impl PartialOrd for RTCDateTime {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl Display for RTCDateTime {
+	/// Prints a `RTCDateTime` formatted according to the [ISO 8601](https://en.wikipedia.org/wiki/ISO_8601) standard.
+	/// The year has at least four digits and every other field two, padded with leading zeros.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 5, hour: 3, minute: 4, second: 9 };
+	/// assert_eq!(format!("{}", datetime), "2019-01-05T03:04:09Z");
+	/// assert_eq!(format!("{}", RTCDateTime { year: 19, ..datetime }), "0019-01-05T03:04:09Z");
+	/// ```
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(
+			f,
+			"{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+			self.year, self.month, self.day, self.hour, self.minute, self.second
+		)
+	}
+}
+
+impl RTCDateTime {
+	/// Check if the `RTCDateTime` instance is a valid date.
+	/// The function takes into account the number of days in months and leap years.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 1, hour: 3, minute: 4, second: 5 };
+	/// assert!(datetime.is_valid());
+	/// assert!(RTCDateTime { day: 31, ..datetime }.is_valid());
+	/// assert!(RTCDateTime { year: 2020, month: 2, day: 29, ..datetime }.is_valid());
+	/// assert!(!RTCDateTime { month: 2, day: 29, ..datetime }.is_valid());
+	/// assert!(!RTCDateTime { day: 0, ..datetime }.is_valid());
+	/// assert!(!RTCDateTime { month: 0, ..datetime }.is_valid());
+	/// assert!(!RTCDateTime { month: 13, ..datetime }.is_valid());
+	/// assert!(!RTCDateTime { second: 60, ..datetime }.is_valid());
+	/// ```
+	#[inline]
+	pub fn is_valid(&self) -> bool {
+		(1..=12).contains(&self.month)
+			&& (1..=RTCDateTime::days_by_month(self.year, self.month)).contains(&self.day)
+			&& self.hour < 24
+			&& self.minute < 60
+			&& self.second < 60
+	}
+
+	/// Returns the first field (from the month down to the second) that is out of range, if any.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{RTCDateTime, RtcField};
+	/// let datetime = RTCDateTime { year: 2019, month: 2, day: 29, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(datetime.invalid_field(), Some(RtcField::Day));
+	/// assert_eq!(RTCDateTime { month: 13, ..datetime }.invalid_field(), Some(RtcField::Month));
+	/// assert_eq!(RTCDateTime { day: 28, second: 70, ..datetime }.invalid_field(), Some(RtcField::Second));
+	/// assert_eq!(RTCDateTime { day: 28, ..datetime }.invalid_field(), None);
+	/// ```
+	pub fn invalid_field(&self) -> Option<RtcField> {
+		if !(1..=12).contains(&self.month) {
+			Some(RtcField::Month)
+		} else if !(1..=RTCDateTime::days_by_month(self.year, self.month)).contains(&self.day) {
+			Some(RtcField::Day)
+		} else if self.hour >= 24 {
+			Some(RtcField::Hour)
+		} else if self.minute >= 60 {
+			Some(RtcField::Minute)
+		} else if self.second >= 60 {
+			Some(RtcField::Second)
+		} else {
+			None
+		}
+	}
+
+	/// Attempt to create a valid `RTCDateTime` from a tuple.
+	/// Returns `Some(RTCDateTime)` in case of success, or `None` if the operation failed.
+	/// The datetime eventually returned is guaranteed to be valid.
+	#[inline]
+	pub fn from_tuple(tuple: &(usize, u8, u8, u8, u8, u8)) -> Option<Self> {
+		let datetime = Self { year: tuple.0, month: tuple.1, day: tuple.2, hour: tuple.3, minute: tuple.4, second: tuple.5 };
+		if datetime.is_valid() {
+			Some(datetime)
+		} else {
+			None
+		}
+	}
+
+	/// Creates a valid `RTCDateTime` from its fields, taking the year as a `u64` so it is never truncated by a cast
+	/// to `usize` on 32 bit targets.
+	/// Fails with `CmosError::InvalidField(RtcField::Year)` if the year doesn't fit in a `usize`, and with the field
+	/// returned by [`invalid_field`] if the date time isn't valid.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{CmosError, RTCDateTime, RtcField};
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(RTCDateTime::from_parts_checked(2019, 1, 2, 3, 4, 5), Ok(datetime));
+	/// assert_eq!(RTCDateTime::from_parts_checked(2019, 2, 30, 3, 4, 5), Err(CmosError::InvalidField(RtcField::Day)));
+	///
+	/// // A year past `u32::MAX` only fits on 64 bit targets. CI also runs the tests on i686 for the error case.
+	/// let year = u64::from(u32::MAX) + 1;
+	/// if cfg!(target_pointer_width = "32") {
+	///     assert_eq!(RTCDateTime::from_parts_checked(year, 1, 2, 3, 4, 5), Err(CmosError::InvalidField(RtcField::Year)));
+	/// } else {
+	///     assert_eq!(RTCDateTime::from_parts_checked(year, 1, 2, 3, 4, 5).map(|datetime| datetime.year as u64), Ok(year));
+	/// }
+	/// ```
+	/// [`invalid_field`]: struct.RTCDateTime.html#method.invalid_field
+	pub fn from_parts_checked(year: u64, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> Result<Self, CmosError> {
+		let year = usize::try_from(year).map_err(|_| CmosError::InvalidField(RtcField::Year))?;
+		let datetime = Self { year, month, day, hour, minute, second };
+		match datetime.invalid_field() {
+			Some(field) => Err(CmosError::InvalidField(field)),
+			None => Ok(datetime),
+		}
+	}
+
+	/// Returns a tuple containing the fields of a `RTCDateTime` by descending order.
+	#[inline]
+	pub fn as_tuple(&self) -> (usize, u8, u8, u8, u8, u8) {
+		(self.year, self.month, self.day, self.hour, self.minute, self.second)
+	}
+
+	/// Returns the number of seconds from `other` to `self`, negative if `other` is later.
+	///
+	/// The count is in the proleptic Gregorian calendar with every day exactly 86400 seconds long: no leap second is
+	/// ever added, as neither the RTC nor this crate model them. A span across a leap second insertion is one second
+	/// shorter than the true elapsed time. Saturates at the bounds of `i64`.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let before = RTCDateTime { year: 2016, month: 2, day: 28, hour: 12, minute: 0, second: 0 };
+	/// let after = RTCDateTime { year: 2016, month: 3, day: 1, hour: 12, minute: 0, second: 0 };
+	/// assert_eq!(after.difference_in_seconds(&before), 2 * 86400);
+	/// assert_eq!(before.difference_in_seconds(&after), -2 * 86400);
+	///
+	/// // A leap second was inserted at 2016-12-31T23:59:60Z, but it isn't counted
+	/// let before = RTCDateTime { year: 2016, month: 12, day: 31, hour: 23, minute: 59, second: 59 };
+	/// let after = RTCDateTime { year: 2017, month: 1, day: 1, hour: 0, minute: 0, second: 0 };
+	/// assert_eq!(after.difference_in_seconds(&before), 1);
+	/// ```
+	pub fn difference_in_seconds(&self, other: &Self) -> i64 {
+		let difference = self.seconds_since_year_zero() - other.seconds_since_year_zero();
+		difference.max(i128::from(i64::MIN)).min(i128::from(i64::MAX)) as i64
+	}
+
+	/// Returns the span of time elapsed from `earlier` to this date time, or an empty span if `earlier` is actually
+	/// later.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{RTCDateTime, RtcDuration};
+	/// let earlier = RTCDateTime { year: 2019, month: 12, day: 31, hour: 22, minute: 30, second: 0 };
+	/// let later = RTCDateTime { minute: 45, second: 30, ..earlier };
+	/// assert_eq!(later.duration_since(&earlier), RtcDuration { days: 0, hours: 0, minutes: 15, seconds: 30 });
+	/// let later = RTCDateTime { year: 2020, month: 1, day: 3, hour: 1, minute: 0, second: 0 };
+	/// assert_eq!(format!("{}", later.duration_since(&earlier)), "2d 02:30:00");
+	/// assert_eq!(earlier.duration_since(&later), RtcDuration::default());
+	/// ```
+	pub fn duration_since(&self, earlier: &Self) -> RtcDuration {
+		let seconds = self.seconds_since_year_zero() - earlier.seconds_since_year_zero();
+		RtcDuration::from_seconds(seconds.max(0).min(i128::from(u64::MAX)) as u64)
+	}
+
+	/// Returns the number of seconds since the Unix epoch, `1970-01-01T00:00:00Z`, negative before it.
+	///
+	/// The RTC is assumed to hold UTC: no time zone is applied, and like Unix time, no leap second is counted.
+	/// Saturates at the bounds of `i64`.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let epoch = RTCDateTime { year: 1970, month: 1, day: 1, hour: 0, minute: 0, second: 0 };
+	/// assert_eq!(epoch.to_unix_timestamp(), 0);
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(datetime.to_unix_timestamp(), 1_546_398_245);
+	/// let datetime = RTCDateTime { year: 1969, month: 12, day: 31, hour: 23, minute: 59, second: 59 };
+	/// assert_eq!(datetime.to_unix_timestamp(), -1);
+	/// let datetime = RTCDateTime { year: 1900, month: 3, day: 1, hour: 0, minute: 0, second: 0 };
+	/// assert_eq!(datetime.to_unix_timestamp(), -2_203_891_200);
+	/// ```
+	pub fn to_unix_timestamp(&self) -> i64 {
+		let timestamp = self.seconds_since_year_zero() - RTCDateTime::days_before_year(1970) * 86400;
+		timestamp.max(i128::from(i64::MIN)).min(i128::from(i64::MAX)) as i64
+	}
+
+	/// Creates a `RTCDateTime` from a number of seconds since the Unix epoch, `1970-01-01T00:00:00Z`, the inverse of
+	/// [`to_unix_timestamp`]. Timestamps before `0000-01-01T00:00:00Z` saturate at [`MIN`](constant.MIN.html).
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// assert_eq!(RTCDateTime::from_unix_timestamp(0).as_tuple(), (1970, 1, 1, 0, 0, 0));
+	/// assert_eq!(RTCDateTime::from_unix_timestamp(-1).as_tuple(), (1969, 12, 31, 23, 59, 59));
+	///
+	/// let dates = [
+	///     RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 },
+	///     RTCDateTime { year: 2020, month: 2, day: 29, hour: 23, minute: 59, second: 59 },
+	///     RTCDateTime { year: 2000, month: 3, day: 1, hour: 0, minute: 0, second: 0 },
+	///     RTCDateTime { year: 1900, month: 12, day: 31, hour: 12, minute: 30, second: 0 },
+	/// ];
+	/// for datetime in dates.iter() {
+	///     let round_trip = RTCDateTime::from_unix_timestamp(datetime.to_unix_timestamp());
+	///     assert_eq!(round_trip, *datetime);
+	///     assert_eq!(round_trip.invalid_field(), None);
+	/// }
+	/// ```
+	/// [`to_unix_timestamp`]: struct.RTCDateTime.html#method.to_unix_timestamp
+	pub fn from_unix_timestamp(secs: i64) -> Self {
+		RTCDateTime::from_seconds_since_year_zero(i128::from(secs) + RTCDateTime::days_before_year(1970) * 86400)
+	}
+
+	/// Returns the number of whole days since the Unix epoch, `1970-01-01`, ignoring the time.
+	/// Dates before the epoch give negative values. Saturates at the bounds of `i64`.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let epoch = RTCDateTime { year: 1970, month: 1, day: 1, hour: 23, minute: 59, second: 59 };
+	/// assert_eq!(epoch.days_since_epoch(), 0);
+	/// assert_eq!(RTCDateTime { day: 2, hour: 0, ..epoch }.days_since_epoch(), 1);
+	/// assert_eq!(RTCDateTime { year: 2000, month: 3, day: 1, ..epoch }.days_since_epoch(), 11_017);
+	/// assert_eq!(RTCDateTime { year: 2019, month: 1, day: 2, ..epoch }.days_since_epoch(), 17_898);
+	/// assert_eq!(RTCDateTime { year: 1969, month: 12, day: 31, ..epoch }.days_since_epoch(), -1);
+	///
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(datetime.days_since_epoch(), datetime.to_unix_timestamp() / 86400);
+	/// ```
+	pub fn days_since_epoch(&self) -> i64 {
+		let days = self.days_since_year_zero() - RTCDateTime::days_before_year(1970);
+		days.max(i128::from(i64::MIN)).min(i128::from(i64::MAX)) as i64
+	}
+
+	/// Returns the NTP timestamp of the date time, in era 0: the seconds since the NTP epoch, `1900-01-01T00:00:00Z`,
+	/// in the upper 32 bits of this 32.32 fixed point value, and the fraction of a second, always 0, in the lower 32
+	/// bits. Like with [`to_unix_timestamp`], the RTC is assumed to hold UTC.
+	/// Era 0 ends at `2036-02-07T06:28:15Z`: date times outside of it saturate at its bounds.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let epoch = RTCDateTime { year: 1900, month: 1, day: 1, hour: 0, minute: 0, second: 0 };
+	/// assert_eq!(epoch.to_ntp_timestamp(), 0);
+	/// let unix_epoch = RTCDateTime { year: 1970, ..epoch };
+	/// assert_eq!(unix_epoch.to_ntp_timestamp(), 2_208_988_800 << 32);
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(datetime.to_ntp_timestamp(), 0xDFD6_A8A5_0000_0000);
+	/// assert_eq!(RTCDateTime { year: 1899, ..datetime }.to_ntp_timestamp(), 0);
+	/// assert_eq!(RTCDateTime { year: 2037, ..datetime }.to_ntp_timestamp(), 0xFFFF_FFFF_0000_0000);
+	/// ```
+	/// [`to_unix_timestamp`]: struct.RTCDateTime.html#method.to_unix_timestamp
+	pub fn to_ntp_timestamp(&self) -> u64 {
+		let seconds = self.seconds_since_year_zero() - RTCDateTime::days_before_year(1900) * 86400;
+		(seconds.max(0).min(i128::from(u32::MAX)) as u64) << 32
+	}
+
+	/// Creates a `RTCDateTime` from an NTP era 0 timestamp, the inverse of [`to_ntp_timestamp`].
+	/// The fraction of a second, in the lower 32 bits, is truncated.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// assert_eq!(RTCDateTime::from_ntp_timestamp(0).as_tuple(), (1900, 1, 1, 0, 0, 0));
+	/// assert_eq!(RTCDateTime::from_ntp_timestamp(0xDFD6_A8A5_8000_0000).as_tuple(), (2019, 1, 2, 3, 4, 5));
+	/// assert_eq!(RTCDateTime::from_ntp_timestamp(u64::MAX).as_tuple(), (2036, 2, 7, 6, 28, 15));
+	///
+	/// let datetime = RTCDateTime { year: 2020, month: 2, day: 29, hour: 23, minute: 59, second: 59 };
+	/// assert_eq!(RTCDateTime::from_ntp_timestamp(datetime.to_ntp_timestamp()), datetime);
+	/// ```
+	/// [`to_ntp_timestamp`]: struct.RTCDateTime.html#method.to_ntp_timestamp
+	pub fn from_ntp_timestamp(timestamp: u64) -> Self {
+		let seconds = i128::from(timestamp >> 32) + RTCDateTime::days_before_year(1900) * 86400;
+		RTCDateTime::from_seconds_since_year_zero(seconds)
+	}
+
+	/// Returns the number of calendar days from `a` to `b`, negative if `b` is earlier. Only the dates are compared,
+	/// so 23:59 to 00:00 the next day is one day.
+	/// Saturates at the bounds of `i64`.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let date = |year, month, day| RTCDateTime { year, month, day, hour: 12, minute: 0, second: 0 };
+	/// assert_eq!(RTCDateTime::days_between(&date(2019, 1, 31), &date(2019, 2, 1)), 1);
+	/// assert_eq!(RTCDateTime::days_between(&date(2019, 2, 1), &date(2019, 1, 31)), -1);
+	/// assert_eq!(RTCDateTime::days_between(&date(2020, 2, 28), &date(2020, 3, 1)), 2);
+	/// assert_eq!(RTCDateTime::days_between(&date(2019, 2, 28), &date(2019, 3, 1)), 1);
+	/// assert_eq!(RTCDateTime::days_between(&date(2019, 1, 1), &date(2021, 1, 1)), 365 + 366);
+	/// assert_eq!(RTCDateTime::days_between(&date(1900, 1, 1), &date(2000, 1, 1)), 36524);
+	/// let late = RTCDateTime { hour: 23, minute: 59, ..date(2019, 1, 1) };
+	/// assert_eq!(RTCDateTime::days_between(&late, &RTCDateTime { hour: 0, ..date(2019, 1, 2) }), 1);
+	/// ```
+	pub fn days_between(a: &Self, b: &Self) -> i64 {
+		(b.days_since_year_zero() - a.days_since_year_zero()).max(i128::from(i64::MIN)).min(i128::from(i64::MAX)) as i64
+	}
+
+	/// Returns the gap between `self` and `other`, in either order, broken into
+	/// `(years, months, days, hours, minutes, seconds)`, like "2 months, 3 days and 4 hours".
+	///
+	/// The fields of the earlier date time are subtracted from the fields of the later one, from the second up to the
+	/// year, borrowing from the next larger unit whenever a difference is negative like in pencil and paper
+	/// subtraction. A borrowed minute is 60 seconds, an hour 60 minutes, a day 24 hours and a year 12 months. Months
+	/// vary in length, so a borrowed month counts as the number of days in the month of the earlier date time: from
+	/// January 31st to March 1st is 1 month and 1 day, whatever the length of February.
+	/// Both date times are expected to be valid.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let start = RTCDateTime { year: 2019, month: 1, day: 15, hour: 3, minute: 4, second: 5 };
+	/// let one_month_later = RTCDateTime { month: 2, ..start };
+	/// assert_eq!(start.components_until(&one_month_later), (0, 1, 0, 0, 0, 0));
+	/// assert_eq!(one_month_later.components_until(&start), (0, 1, 0, 0, 0, 0));
+	///
+	/// let end = RTCDateTime { year: 2021, month: 3, day: 18, hour: 7, minute: 9, second: 11 };
+	/// assert_eq!(start.components_until(&end), (2, 2, 3, 4, 5, 6));
+	/// // Every field borrows from the next one
+	/// let end = RTCDateTime { year: 2020, month: 1, day: 15, hour: 3, minute: 4, second: 4 };
+	/// assert_eq!(start.components_until(&end), (0, 11, 30, 23, 59, 59));
+	///
+	/// let start = RTCDateTime { year: 2019, month: 1, day: 31, hour: 0, minute: 0, second: 0 };
+	/// assert_eq!(start.components_until(&RTCDateTime { month: 3, day: 1, ..start }), (0, 1, 1, 0, 0, 0));
+	/// ```
+	pub fn components_until(&self, other: &Self) -> (u64, u8, u8, u8, u8, u8) {
+		let (start, end) = if self <= other { (self, other) } else { (other, self) };
+
+		// Subtracts a field, borrowing `base` from the next larger unit if the difference is negative
+		let subtract = |end: i64, start: i64, borrowed: i64, base: i64| {
+			let difference = end - start - borrowed;
+			if difference < 0 {
+				(difference + base, 1)
+			} else {
+				(difference, 0)
+			}
+		};
+		let (seconds, borrowed) = subtract(i64::from(end.second), i64::from(start.second), 0, 60);
+		let (minutes, borrowed) = subtract(i64::from(end.minute), i64::from(start.minute), borrowed, 60);
+		let (hours, borrowed) = subtract(i64::from(end.hour), i64::from(start.hour), borrowed, 24);
+		let month_length = i64::from(RTCDateTime::days_by_month(start.year, start.month));
+		let (days, borrowed) = subtract(i64::from(end.day), i64::from(start.day), borrowed, month_length);
+		let (months, borrowed) = subtract(i64::from(end.month), i64::from(start.month), borrowed, 12);
+		// `end` is not earlier than `start`, so the borrow can't make the years negative
+		let years = (end.year - start.year) as u64 - borrowed as u64;
+		(years, months as u8, days as u8, hours as u8, minutes as u8, seconds as u8)
+	}
+
+	/// Adds `n` seconds to the second field only, wrapping at 60.
+	/// Returns the new `RTCDateTime` and whether the second wrapped, i.e. should carry into the minute, which is left
+	/// untouched.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 58 };
+	/// assert_eq!(datetime.wrapping_add_seconds(1), (RTCDateTime { second: 59, ..datetime }, false));
+	/// assert_eq!(datetime.wrapping_add_seconds(3), (RTCDateTime { second: 1, ..datetime }, true));
+	/// ```
+	pub fn wrapping_add_seconds(&self, n: u8) -> (Self, bool) {
+		let (second, carry) = RTCDateTime::wrapping_add_field(self.second, n, 0, 60);
+		(Self { second, ..*self }, carry)
+	}
+
+	/// Adds `n` minutes to the minute field only, wrapping at 60.
+	/// Returns the new `RTCDateTime` and whether the minute wrapped into the hour, which is left untouched.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 58, second: 5 };
+	/// assert_eq!(datetime.wrapping_add_minutes(1), (RTCDateTime { minute: 59, ..datetime }, false));
+	/// assert_eq!(datetime.wrapping_add_minutes(2), (RTCDateTime { minute: 0, ..datetime }, true));
+	/// ```
+	pub fn wrapping_add_minutes(&self, n: u8) -> (Self, bool) {
+		let (minute, carry) = RTCDateTime::wrapping_add_field(self.minute, n, 0, 60);
+		(Self { minute, ..*self }, carry)
+	}
+
+	/// Adds `n` hours to the hour field only, wrapping at 24.
+	/// Returns the new `RTCDateTime` and whether the hour wrapped into the day, which is left untouched.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 22, minute: 4, second: 5 };
+	/// assert_eq!(datetime.wrapping_add_hours(1), (RTCDateTime { hour: 23, ..datetime }, false));
+	/// assert_eq!(datetime.wrapping_add_hours(3), (RTCDateTime { hour: 1, ..datetime }, true));
+	/// ```
+	pub fn wrapping_add_hours(&self, n: u8) -> (Self, bool) {
+		let (hour, carry) = RTCDateTime::wrapping_add_field(self.hour, n, 0, 24);
+		(Self { hour, ..*self }, carry)
+	}
+
+	/// Adds `n` days to the day field only, wrapping at the end of the month (starting again at 1).
+	/// Returns the new `RTCDateTime` and whether the day wrapped into the month, which is left untouched.
+	/// An invalid month is returned unchanged, without carry.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let datetime = RTCDateTime { year: 2020, month: 2, day: 28, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(datetime.wrapping_add_days(1), (RTCDateTime { day: 29, ..datetime }, false));
+	/// assert_eq!(datetime.wrapping_add_days(2), (RTCDateTime { day: 1, ..datetime }, true));
+	/// ```
+	pub fn wrapping_add_days(&self, n: u8) -> (Self, bool) {
+		let (day, carry) =
+			RTCDateTime::wrapping_add_field(self.day, n, 1, RTCDateTime::days_by_month(self.year, self.month));
+		(Self { day, ..*self }, carry)
+	}
+
+	/// Adds `n` months to the month field only, wrapping after December (starting again at 1).
+	/// Returns the new `RTCDateTime` and whether the month wrapped into the year, which is left untouched.
+	/// The day is left untouched too, so it may end up past the end of the new month.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let datetime = RTCDateTime { year: 2019, month: 11, day: 2, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(datetime.wrapping_add_months(1), (RTCDateTime { month: 12, ..datetime }, false));
+	/// assert_eq!(datetime.wrapping_add_months(3), (RTCDateTime { month: 2, ..datetime }, true));
+	/// ```
+	pub fn wrapping_add_months(&self, n: u8) -> (Self, bool) {
+		let (month, carry) = RTCDateTime::wrapping_add_field(self.month, n, 1, 12);
+		(Self { month, ..*self }, carry)
+	}
+
+	/// Advances the date time by `secs` seconds, carrying into the minutes, hours, days, months and years, taking the
+	/// length of each month and leap years into account. Saturates at [`MAX`](constant.MAX.html).
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{RTCDateTime, MAX};
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 31, hour: 23, minute: 59, second: 30 };
+	/// assert_eq!(datetime.add_seconds(45).as_tuple(), (2019, 2, 1, 0, 0, 15));
+	/// let datetime = RTCDateTime { year: 2020, month: 2, day: 28, hour: 12, minute: 0, second: 0 };
+	/// assert_eq!(datetime.add_seconds(86400).as_tuple(), (2020, 2, 29, 12, 0, 0));
+	/// assert_eq!(datetime.add_seconds(2 * 86400).as_tuple(), (2020, 3, 1, 12, 0, 0));
+	/// assert_eq!(RTCDateTime { year: 2019, ..datetime }.add_seconds(86400).as_tuple(), (2019, 3, 1, 12, 0, 0));
+	/// assert_eq!(MAX.add_seconds(1), MAX);
+	/// ```
+	pub fn add_seconds(self, secs: u64) -> RTCDateTime {
+		RTCDateTime::from_seconds_since_year_zero(self.seconds_since_year_zero() + i128::from(secs))
+	}
+
+	/// Advances the date time by `secs` seconds like [`add_seconds`], but returns `None` instead of saturating if the
+	/// result is past the last second of year `usize::MAX`.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{RTCDateTime, MAX};
+	/// let datetime = RTCDateTime { year: 2019, month: 12, day: 31, hour: 23, minute: 59, second: 59 };
+	/// assert_eq!(datetime.checked_add_seconds(1).unwrap().as_tuple(), (2020, 1, 1, 0, 0, 0));
+	/// assert_eq!(MAX.checked_add_seconds(0), Some(MAX));
+	/// assert_eq!(MAX.checked_add_seconds(1), None);
+	/// assert_eq!(RTCDateTime { second: 58, ..MAX }.checked_add_seconds(1), Some(MAX));
+	/// ```
+	/// [`add_seconds`]: struct.RTCDateTime.html#method.add_seconds
+	pub fn checked_add_seconds(self, secs: u64) -> Option<RTCDateTime> {
+		RTCDateTime::checked_from_seconds_since_year_zero(self.seconds_since_year_zero() + i128::from(secs))
+	}
+
+	/// Moves the date time back by `secs` seconds, borrowing from the minutes, hours, days, months and years.
+	/// Returns `None` if the result is before `0000-01-01T00:00:00`.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{RTCDateTime, MIN};
+	/// let datetime = RTCDateTime { year: 2020, month: 3, day: 1, hour: 0, minute: 0, second: 0 };
+	/// assert_eq!(datetime.checked_sub_seconds(1).unwrap().as_tuple(), (2020, 2, 29, 23, 59, 59));
+	/// assert_eq!(MIN.checked_sub_seconds(0), Some(MIN));
+	/// assert_eq!(MIN.checked_sub_seconds(1), None);
+	/// assert_eq!(RTCDateTime { second: 1, ..MIN }.checked_sub_seconds(1), Some(MIN));
+	/// ```
+	pub fn checked_sub_seconds(self, secs: u64) -> Option<RTCDateTime> {
+		RTCDateTime::checked_from_seconds_since_year_zero(self.seconds_since_year_zero() - i128::from(secs))
+	}
+
+	/// Advances the date by `days` whole days, rolling over into the next months and years as needed, with the time
+	/// fields untouched. Saturates at [`MAX`](constant.MAX.html).
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{RTCDateTime, MAX};
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 31, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(datetime.add_days(1).as_tuple(), (2019, 2, 1, 3, 4, 5));
+	/// assert_eq!(datetime.add_days(29).as_tuple(), (2019, 3, 1, 3, 4, 5));
+	/// assert_eq!(RTCDateTime { year: 2020, ..datetime }.add_days(29).as_tuple(), (2020, 2, 29, 3, 4, 5));
+	/// assert_eq!(RTCDateTime { year: 2020, ..datetime }.add_days(30).as_tuple(), (2020, 3, 1, 3, 4, 5));
+	/// assert_eq!(datetime.add_days(366).as_tuple(), (2020, 2, 1, 3, 4, 5));
+	/// assert_eq!(MAX.add_days(1), MAX);
+	/// ```
+	pub fn add_days(self, days: u32) -> RTCDateTime {
+		RTCDateTime::from_seconds_since_year_zero(self.seconds_since_year_zero() + i128::from(days) * 86400)
+	}
+
+	/// Returns the same time on the next day, rolling over into the next month and year as needed.
+	/// Saturates at [`MAX`](constant.MAX.html).
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let datetime = RTCDateTime { year: 2019, month: 12, day: 31, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(datetime.tomorrow().as_tuple(), (2020, 1, 1, 3, 4, 5));
+	/// assert_eq!(RTCDateTime { month: 2, day: 28, ..datetime }.tomorrow().as_tuple(), (2019, 3, 1, 3, 4, 5));
+	/// assert_eq!(RTCDateTime { year: 2020, month: 2, day: 28, ..datetime }.tomorrow().as_tuple(), (2020, 2, 29, 3, 4, 5));
+	/// ```
+	pub fn tomorrow(&self) -> Self { self.add_days(1) }
+
+	/// Returns the same time on the previous day, rolling back into the previous month and year as needed.
+	/// Saturates at [`MIN`](constant.MIN.html).
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{RTCDateTime, MIN};
+	/// let datetime = RTCDateTime { year: 2020, month: 1, day: 1, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(datetime.yesterday().as_tuple(), (2019, 12, 31, 3, 4, 5));
+	/// assert_eq!(RTCDateTime { month: 3, ..datetime }.yesterday().as_tuple(), (2020, 2, 29, 3, 4, 5));
+	/// assert_eq!(RTCDateTime { year: 2019, month: 3, ..datetime }.yesterday().as_tuple(), (2019, 2, 28, 3, 4, 5));
+	/// assert_eq!(RTCDateTime { hour: 23, ..MIN }.yesterday(), MIN);
+	/// ```
+	pub fn yesterday(&self) -> Self { RTCDateTime::from_seconds_since_year_zero(self.seconds_since_year_zero() - 86400) }
+
+	/// Rounds to the nearest multiple of `interval` minutes, counted from midnight, carrying into the following day
+	/// (and month and year) as needed. The seconds are taken into account and end up at 0; a date time exactly half
+	/// way between two multiples is rounded up.
+	///
+	/// Intervals dividing 60 give the usual boundaries (every quarter hour for 15). Other intervals keep counting
+	/// from midnight, and the next midnight is always a boundary, which makes the last interval of the day shorter.
+	/// An interval of 0 only drops the seconds. Saturates at [`MAX`](constant.MAX.html).
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let datetime = RTCDateTime { year: 2019, month: 12, day: 31, hour: 10, minute: 7, second: 0 };
+	/// assert_eq!(datetime.snap_to_minutes(15).as_tuple(), (2019, 12, 31, 10, 0, 0));
+	/// assert_eq!(RTCDateTime { minute: 8, ..datetime }.snap_to_minutes(15).as_tuple(), (2019, 12, 31, 10, 15, 0));
+	/// assert_eq!(RTCDateTime { second: 30, ..datetime }.snap_to_minutes(15).as_tuple(), (2019, 12, 31, 10, 15, 0));
+	/// let late = RTCDateTime { hour: 23, minute: 53, ..datetime };
+	/// assert_eq!(late.snap_to_minutes(15).as_tuple(), (2020, 1, 1, 0, 0, 0));
+	/// ```
+	pub fn snap_to_minutes(self, interval: u8) -> Self {
+		let interval = i128::from(interval) * 60;
+		let seconds = self.seconds_since_year_zero();
+		let day_start = seconds - seconds % 86400;
+		let time = seconds - day_start;
+		let snapped = if interval == 0 {
+			time - time % 60
+		} else {
+			let lower = time - time % interval;
+			let upper = (lower + interval).min(86400);
+			if time - lower < upper - time {
+				lower
+			} else {
+				upper
+			}
+		};
+		RTCDateTime::from_seconds_since_year_zero(day_start + snapped)
+	}
+
+	/// Estimates the drift of the RTC, in parts per million, against a trusted reference.
+	/// `self` is the RTC reading and `reference` the trusted time taken at the same instant, where both clocks were
+	/// in sync `elapsed_reference_secs` seconds ago (as measured by the reference).
+	/// A positive value means the RTC runs fast, a negative value means it runs slow.
+	/// Returns `0` if `elapsed_reference_secs` is `0`.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let reference = RTCDateTime { year: 2019, month: 1, day: 2, hour: 0, minute: 0, second: 0 };
+	/// // The RTC gained 1 second over 100000 seconds
+	/// let rtc = RTCDateTime { second: 1, ..reference };
+	/// assert_eq!(rtc.drift_ppm(&reference, 100_000), 10);
+	/// assert_eq!(reference.drift_ppm(&rtc, 100_000), -10);
+	/// assert_eq!(reference.drift_ppm(&reference, 100_000), 0);
+	/// ```
+	pub fn drift_ppm(&self, reference: &Self, elapsed_reference_secs: u64) -> i64 {
+		if elapsed_reference_secs == 0 {
+			return 0;
+		}
+		let offset = self.seconds_since_year_zero() - reference.seconds_since_year_zero();
+		let ppm = offset * 1_000_000 / i128::from(elapsed_reference_secs);
+		ppm.max(i128::from(i64::MIN)).min(i128::from(i64::MAX)) as i64
+	}
+
+	/// Returns how much of the year has elapsed, from `0.0` at midnight on January 1st to just under `1.0` at the
+	/// last second of December 31st. Leap years count 366 days.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let new_year = RTCDateTime { year: 2020, month: 1, day: 1, hour: 0, minute: 0, second: 0 };
+	/// assert_eq!(new_year.fraction_of_year_elapsed(), 0.0);
+	/// let last_second = RTCDateTime { month: 12, day: 31, hour: 23, minute: 59, second: 59, ..new_year };
+	/// assert!((last_second.fraction_of_year_elapsed() - 1.0).abs() < 1e-6);
+	/// // July 2nd at noon is half way through 2019, the extra day of 2020 moves it to July 2nd at midnight
+	/// let half_way = RTCDateTime { year: 2019, month: 7, day: 2, hour: 12, minute: 0, second: 0 };
+	/// assert!((half_way.fraction_of_year_elapsed() - 0.5).abs() < 1e-6);
+	/// let half_way = RTCDateTime { year: 2020, hour: 0, ..half_way };
+	/// assert!((half_way.fraction_of_year_elapsed() - 0.5).abs() < 1e-6);
+	/// ```
+	pub fn fraction_of_year_elapsed(&self) -> f32 {
+		let year_start = RTCDateTime::days_before_year(self.year) * 86400;
+		let year_length = (337 + i128::from(RTCDateTime::days_by_month(self.year, 2))) * 86400;
+		((self.seconds_since_year_zero() - year_start) as f64 / year_length as f64) as f32
+	}
+
+	/// Returns how much of the day has elapsed, from `0.0` at midnight to just under `1.0` at 23:59:59.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let midnight = RTCDateTime { year: 2019, month: 1, day: 2, hour: 0, minute: 0, second: 0 };
+	/// assert_eq!(midnight.fraction_of_day_elapsed(), 0.0);
+	/// assert_eq!(RTCDateTime { hour: 18, ..midnight }.fraction_of_day_elapsed(), 0.75);
+	/// let last_second = RTCDateTime { hour: 23, minute: 59, second: 59, ..midnight };
+	/// assert!((last_second.fraction_of_day_elapsed() - 1.0).abs() < 1e-4);
+	/// ```
+	pub fn fraction_of_day_elapsed(&self) -> f32 {
+		let seconds = u32::from(self.hour) * 3600 + u32::from(self.minute) * 60 + u32::from(self.second);
+		seconds as f32 / 86400.0
+	}
+
+	/// Packs the date into a sortable decimal number, `YYYYMMDD`.
+	/// Only meaningful for years below 10000.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(datetime.yyyymmdd(), 20190102);
+	/// ```
+	#[inline]
+	pub fn yyyymmdd(&self) -> u32 {
+		(self.year as u32).wrapping_mul(10000).wrapping_add(u32::from(self.month) * 100 + u32::from(self.day))
+	}
+
+	/// Packs the time into a sortable decimal number, `HHMMSS`.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(datetime.hhmmss(), 30405);
+	/// ```
+	#[inline]
+	pub fn hhmmss(&self) -> u32 { u32::from(self.hour) * 10000 + u32::from(self.minute) * 100 + u32::from(self.second) }
+
+	/// Attempt to create a valid `RTCDateTime` from a `YYYYMMDD` date and a `HHMMSS` time.
+	/// Returns `None` if the resulting datetime is not valid.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(RTCDateTime::from_yyyymmdd_hhmmss(20190102, 30405), Some(datetime));
+	/// assert_eq!(RTCDateTime::from_yyyymmdd_hhmmss(20191302, 30405), None);
+	/// assert_eq!(RTCDateTime::from_yyyymmdd_hhmmss(20190102, 30465), None);
+	/// ```
+	pub fn from_yyyymmdd_hhmmss(yyyymmdd: u32, hhmmss: u32) -> Option<Self> {
+		if hhmmss > 999_999 {
+			return None;
+		}
+		RTCDateTime::from_tuple(&(
+			(yyyymmdd / 10000) as usize,
+			(yyyymmdd / 100 % 100) as u8,
+			(yyyymmdd % 100) as u8,
+			(hhmmss / 10000) as u8,
+			(hhmmss / 100 % 100) as u8,
+			(hhmmss % 100) as u8,
+		))
+	}
+
+	/// Creates a valid `RTCDateTime` from a `YYYY-MM-DD` date string and a `HH:MM:SS` time string, as found in config
+	/// files that keep them apart. The seconds can be left out (`HH:MM`) and default to 0. Fields don't need
+	/// leading zeros.
+	///
+	/// Fails with the same [`ParseError`] as [`from_iso8601`], with indices into the string the byte is in:
+	/// [`ParseError::Length`] if a string has too few fields, [`ParseError::Separator`] at an extra or doubled
+	/// separator, [`ParseError::NotADigit`] at a byte in a field that isn't a decimal digit, and
+	/// [`ParseError::OutOfRange`] if a field is out of range or the day doesn't exist in the month.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{ParseError, RTCDateTime, RtcField};
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(RTCDateTime::from_date_time_strs("2019-01-02", "03:04:05"), Ok(datetime));
+	/// assert_eq!(RTCDateTime::from_date_time_strs("2019-1-2", "3:04"), Ok(RTCDateTime { second: 0, ..datetime }));
+	///
+	/// assert_eq!(RTCDateTime::from_date_time_strs("2019/01/02", "03:04:05"), Err(ParseError::NotADigit(4)));
+	/// assert_eq!(RTCDateTime::from_date_time_strs("2019-01-02", "03:04:05:06"), Err(ParseError::Separator(8)));
+	/// assert_eq!(RTCDateTime::from_date_time_strs("2019-01-02", "03::04"), Err(ParseError::Separator(3)));
+	/// assert_eq!(RTCDateTime::from_date_time_strs("2019-01-02", "03"), Err(ParseError::Length));
+	/// assert_eq!(RTCDateTime::from_date_time_strs("2019-01-02", "03:+4"), Err(ParseError::NotADigit(3)));
+	/// assert_eq!(RTCDateTime::from_date_time_strs("2019-02-29", "03:04"), Err(ParseError::OutOfRange(RtcField::Day)));
+	/// assert_eq!(RTCDateTime::from_date_time_strs("2019-01-02", "03:300"), Err(ParseError::OutOfRange(RtcField::Minute)));
+	/// ```
+	/// [`ParseError`]: enum.ParseError.html
+	/// [`from_iso8601`]: struct.RTCDateTime.html#method.from_iso8601
+	/// [`ParseError::Length`]: enum.ParseError.html#variant.Length
+	/// [`ParseError::Separator`]: enum.ParseError.html#variant.Separator
+	/// [`ParseError::NotADigit`]: enum.ParseError.html#variant.NotADigit
+	/// [`ParseError::OutOfRange`]: enum.ParseError.html#variant.OutOfRange
+	pub fn from_date_time_strs(date: &str, time: &str) -> Result<Self, ParseError> {
+		let mut date_fields = [0; 3];
+		if RTCDateTime::parse_fields(date, '-', &mut date_fields, [RtcField::Year, RtcField::Month, RtcField::Day])? != 3 {
+			return Err(ParseError::Length);
+		}
+		let mut time_fields = [0; 3];
+		if RTCDateTime::parse_fields(time, ':', &mut time_fields, [RtcField::Hour, RtcField::Minute, RtcField::Second])? < 2
+		{
+			return Err(ParseError::Length);
+		}
+
+		let narrow = |value: usize, field| u8::try_from(value).map_err(|_| ParseError::OutOfRange(field));
+		let datetime = Self {
+			year: date_fields[0],
+			month: narrow(date_fields[1], RtcField::Month)?,
+			day: narrow(date_fields[2], RtcField::Day)?,
+			hour: narrow(time_fields[0], RtcField::Hour)?,
+			minute: narrow(time_fields[1], RtcField::Minute)?,
+			second: narrow(time_fields[2], RtcField::Second)?,
+		};
+		match datetime.invalid_field() {
+			Some(field) => Err(ParseError::OutOfRange(field)),
+			None => Ok(datetime),
+		}
+	}
+
+	/// Parses a date time in the format printed by `Display`: `YYYY-MM-DDTHH:MM:SSZ`.
+	/// The year has at least four digits (more for years past 9999) and every other field exactly two.
+	///
+	/// Fails with [`ParseError::Length`] if the string doesn't have the length of that layout,
+	/// [`ParseError::Separator`] or [`ParseError::NotADigit`] with the index of the first unexpected byte, and
+	/// [`ParseError::OutOfRange`] if a field is out of range or the day doesn't exist in the month.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{ParseError, RTCDateTime, RtcField};
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(RTCDateTime::from_iso8601("2019-01-02T03:04:05Z"), Ok(datetime));
+	/// assert_eq!(RTCDateTime::from_iso8601("12019-01-02T03:04:05Z"), Ok(RTCDateTime { year: 12019, ..datetime }));
+	/// assert_eq!(RTCDateTime::from_iso8601(&format!("{}", datetime)), Ok(datetime));
+	///
+	/// assert_eq!(RTCDateTime::from_iso8601("2019-01-02 03:04:05Z"), Err(ParseError::Separator(10)));
+	/// assert_eq!(RTCDateTime::from_iso8601("2019-01-02T03:04:0xZ"), Err(ParseError::NotADigit(18)));
+	/// assert_eq!(RTCDateTime::from_iso8601("2019-01-02T03:04:05"), Err(ParseError::Length));
+	/// assert_eq!(RTCDateTime::from_iso8601("2019-13-02T03:04:05Z"), Err(ParseError::OutOfRange(RtcField::Month)));
+	/// assert_eq!(RTCDateTime::from_iso8601("2019-02-29T03:04:05Z"), Err(ParseError::OutOfRange(RtcField::Day)));
+	/// ```
+	/// [`ParseError::Length`]: enum.ParseError.html#variant.Length
+	/// [`ParseError::Separator`]: enum.ParseError.html#variant.Separator
+	/// [`ParseError::NotADigit`]: enum.ParseError.html#variant.NotADigit
+	/// [`ParseError::OutOfRange`]: enum.ParseError.html#variant.OutOfRange
+	pub fn from_iso8601(s: &str) -> Result<Self, ParseError> {
+		// Layout after the year, with `0` standing for a digit
+		const LAYOUT: &[u8] = b"-00-00T00:00:00Z";
+
+		let bytes = s.as_bytes();
+		let year_len = bytes.iter().take_while(|byte| byte.is_ascii_digit()).count().max(4);
+		if bytes.len() != year_len + LAYOUT.len() {
+			return Err(ParseError::Length);
+		}
+		for (index, &byte) in bytes.iter().enumerate() {
+			let expected = index.checked_sub(year_len).map_or(b'0', |offset| LAYOUT[offset]);
+			if expected == b'0' && !byte.is_ascii_digit() {
+				return Err(ParseError::NotADigit(index));
+			} else if expected != b'0' && byte != expected {
+				return Err(ParseError::Separator(index));
+			}
+		}
+
+		let number = |start: usize, len: usize| {
+			bytes[start..start + len]
+				.iter()
+				.try_fold(0usize, |value, byte| value.checked_mul(10)?.checked_add(usize::from(byte - b'0')))
+		};
+		// The other fields have two digits, so they always fit in a `u8`
+		let field = |offset: usize| number(year_len + offset, 2).unwrap_or(0) as u8;
+		let datetime = Self {
+			year: number(0, year_len).ok_or(ParseError::OutOfRange(RtcField::Year))?,
+			month: field(1),
+			day: field(4),
+			hour: field(7),
+			minute: field(10),
+			second: field(13),
+		};
+		match datetime.invalid_field() {
+			Some(field) => Err(ParseError::OutOfRange(field)),
+			None => Ok(datetime),
+		}
+	}
+
+	/// Packs the date time into 7 bytes for the wire: the year in big endian over 2 bytes, then the month, day,
+	/// hour, minute and second, one byte each, readable as is in a hex dump.
+	/// The format is limited to years up to 65535: later years are saturated to 65535.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(datetime.to_bytes(), [0x07, 0xE3, 1, 2, 3, 4, 5]);
+	/// assert_eq!(RTCDateTime { year: 70000, ..datetime }.to_bytes(), [0xFF, 0xFF, 1, 2, 3, 4, 5]);
+	/// ```
+	pub fn to_bytes(&self) -> [u8; 7] {
+		let year = self.year.min(0xFFFF) as u16;
+		[(year >> 8) as u8, year as u8, self.month, self.day, self.hour, self.minute, self.second]
+	}
+
+	/// Unpacks a date time packed by [`to_bytes`].
+	/// Returns `None` if the bytes don't make a valid date time.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let datetime = RTCDateTime { year: 2020, month: 2, day: 29, hour: 23, minute: 59, second: 59 };
+	/// assert_eq!(RTCDateTime::from_bytes(datetime.to_bytes()), Some(datetime));
+	/// let last = RTCDateTime { year: 65535, month: 12, day: 31, ..datetime };
+	/// assert_eq!(RTCDateTime::from_bytes(last.to_bytes()), Some(last));
+	/// assert_eq!(RTCDateTime::from_bytes([0x07, 0xE3, 2, 29, 3, 4, 5]), None);
+	/// ```
+	/// [`to_bytes`]: struct.RTCDateTime.html#method.to_bytes
+	pub fn from_bytes(bytes: [u8; 7]) -> Option<Self> {
+		let datetime = Self {
+			year: usize::from(u16::from(bytes[0]) << 8 | u16::from(bytes[1])),
+			month: bytes[2],
+			day: bytes[3],
+			hour: bytes[4],
+			minute: bytes[5],
+			second: bytes[6],
+		};
+		match datetime.invalid_field() {
+			Some(_) => None,
+			None => Some(datetime),
+		}
+	}
+
+	/// Writes a fixed width, zero padded `YYYYMMDDHHMMSS` key into `buf`, whose lexical order is the chronological
+	/// order. Returns the number of bytes written, or `0` if `buf` is too small.
+	///
+	/// The buffer needs 14 bytes for years below 10000. Longer years take more digits, and then only sort
+	/// lexically against keys of the same length.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let earlier = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// let later = RTCDateTime { year: 2019, month: 10, day: 1, hour: 0, minute: 0, second: 0 };
+	/// let (mut earlier_key, mut later_key) = ([0; 14], [0; 14]);
+	/// assert_eq!(earlier.sort_key(&mut earlier_key), 14);
+	/// assert_eq!(later.sort_key(&mut later_key), 14);
+	/// assert_eq!(&earlier_key, b"20190102030405");
+	/// assert_eq!(earlier_key.cmp(&later_key), earlier.cmp(&later));
+	/// assert_eq!(earlier.sort_key(&mut [0; 13]), 0);
+	/// ```
+	pub fn sort_key(&self, buf: &mut [u8]) -> usize {
+		let mut writer = SliceWriter::new(buf);
+		match write!(
+			writer,
+			"{:04}{:02}{:02}{:02}{:02}{:02}",
+			self.year, self.month, self.day, self.hour, self.minute, self.second
+		) {
+			Ok(()) => writer.len(),
+			Err(_) => 0,
+		}
+	}
+
+	/// Returns the day of the year, from 1 for January 1st to 365, or 366 in leap years, for December 31st.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 1, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(datetime.ordinal(), 1);
+	/// assert_eq!(RTCDateTime { month: 3, ..datetime }.ordinal(), 60);
+	/// assert_eq!(RTCDateTime { year: 2020, month: 3, ..datetime }.ordinal(), 61);
+	/// assert_eq!(RTCDateTime { month: 12, day: 31, ..datetime }.ordinal(), 365);
+	/// assert_eq!(RTCDateTime { year: 2020, month: 12, day: 31, ..datetime }.ordinal(), 366);
+	/// ```
+	pub fn ordinal(&self) -> u16 { (RTCDateTime::days_before_month(self.year, self.month) + i128::from(self.day)) as u16 }
+
+	/// Writes the ISO 8601 ordinal date, `YYYY-DDD` with the day of the year from 001, into `buf`.
+	/// Returns the number of bytes written, or `0` if `buf` is too small.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let mut buf = [0; 8];
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(datetime.to_iso_ordinal(&mut buf), 8);
+	/// assert_eq!(&buf, b"2019-002");
+	/// let datetime = RTCDateTime { month: 12, day: 31, ..datetime };
+	/// datetime.to_iso_ordinal(&mut buf);
+	/// assert_eq!(&buf, b"2019-365");
+	/// let datetime = RTCDateTime { year: 2020, ..datetime };
+	/// datetime.to_iso_ordinal(&mut buf);
+	/// assert_eq!(&buf, b"2020-366");
+	/// assert_eq!(datetime.to_iso_ordinal(&mut [0; 7]), 0);
+	/// ```
+	pub fn to_iso_ordinal(&self, buf: &mut [u8]) -> usize {
+		let mut writer = SliceWriter::new(buf);
+		match write!(writer, "{:04}-{:03}", self.year, self.ordinal()) {
+			Ok(()) => writer.len(),
+			Err(_) => 0,
+		}
+	}
+
+	/// Checks whether the `RTCDateTime` looks like an RTC that was reset and never set.
+	/// This is a heuristic: it is true within the first hour of January 1st of one of the common BIOS default years
+	/// ([`UNSET_YEARS`]: 1970, 1980 and 2000).
+	/// Use [`looks_unset_with`] to provide other default years.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let reset = RTCDateTime { year: 2000, month: 1, day: 1, hour: 0, minute: 0, second: 0 };
+	/// assert!(reset.looks_unset());
+	/// let set = RTCDateTime { year: 2019, month: 6, day: 15, hour: 14, minute: 23, second: 10 };
+	/// assert!(!set.looks_unset());
+	/// ```
+	/// [`UNSET_YEARS`]: constant.UNSET_YEARS.html
+	/// [`looks_unset_with`]: struct.RTCDateTime.html#method.looks_unset_with
+	#[inline]
+	pub fn looks_unset(&self) -> bool { self.looks_unset_with(&UNSET_YEARS) }
+
+	/// Checks whether the `RTCDateTime` looks like an RTC that was reset to one of the given default years.
+	/// See [`looks_unset`] for the heuristic.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let reset = RTCDateTime { year: 1990, month: 1, day: 1, hour: 0, minute: 3, second: 10 };
+	/// assert!(reset.looks_unset_with(&[1990]));
+	/// assert!(!reset.looks_unset());
+	/// ```
+	/// [`looks_unset`]: struct.RTCDateTime.html#method.looks_unset
+	pub fn looks_unset_with(&self, default_years: &[usize]) -> bool {
+		default_years.contains(&self.year) && self.month == 1 && self.day == 1 && self.hour == 0
+	}
+
+	/// Checks whether the `RTCDateTime` falls on the same day as `today`, ignoring the time.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let today = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// assert!(RTCDateTime { hour: 23, ..today }.is_today(&today));
+	/// assert!(!RTCDateTime { day: 3, ..today }.is_today(&today));
+	/// assert!(!RTCDateTime { year: 2018, ..today }.is_today(&today));
+	/// ```
+	#[inline]
+	pub fn is_today(&self, today: &Self) -> bool { self.is_this_month(today) && self.day == today.day }
+
+	/// Checks whether the `RTCDateTime` falls in the same month (of the same year) as `today`.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let today = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// assert!(RTCDateTime { day: 31, ..today }.is_this_month(&today));
+	/// assert!(!RTCDateTime { month: 2, ..today }.is_this_month(&today));
+	/// assert!(!RTCDateTime { year: 2018, ..today }.is_this_month(&today));
+	/// ```
+	#[inline]
+	pub fn is_this_month(&self, today: &Self) -> bool { self.year == today.year && self.month == today.month }
+
+	/// Returns the bytes [`CMOS::write_rtc`] writes for this date time in the given format, to inspect the encoding
+	/// without the hardware.
+	/// The array holds registers `0x00` to `0x09` in the standard layout; the alarm registers (`0x01`, `0x03` and
+	/// `0x05`) aren't written and are left at 0. The century byte is returned alongside with `CenturyRegister`, and
+	/// is `None` with `CurrentYear`, where only the two digit year is stored.
+	/// The fields are not validated: an invalid date time gives meaningless bytes.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{CMOSCenturyHandler, RTCDateTime, RtcFormat};
+	/// // Wednesday 2019-01-02T15:04:05
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 15, minute: 4, second: 5 };
+	/// let bcd_24_hour = RtcFormat { binary: false, hour_24: true };
+	/// assert_eq!(
+	///     datetime.to_rtc_bytes(bcd_24_hour, CMOSCenturyHandler::CenturyRegister(0x32)),
+	///     ([0x05, 0, 0x04, 0, 0x15, 0, 0x04, 0x02, 0x01, 0x19], Some(0x20))
+	/// );
+	/// let binary_12_hour = RtcFormat { binary: true, hour_24: false };
+	/// assert_eq!(
+	///     datetime.to_rtc_bytes(binary_12_hour, CMOSCenturyHandler::CurrentYear(2019)),
+	///     ([5, 0, 4, 0, 0x80 | 3, 0, 4, 2, 1, 19], None)
+	/// );
+	/// ```
+	/// [`CMOS::write_rtc`]: struct.CMOS.html#method.write_rtc
+	pub fn to_rtc_bytes(&self, format: RtcFormat, century_handler: CMOSCenturyHandler) -> ([u8; 10], Option<u8>) {
+		let raw = self.encode(format);
+		let bytes = [raw.second, 0, raw.minute, 0, raw.hour, 0, raw.weekday, raw.day, raw.month, raw.year];
+		match century_handler {
+			CMOSCenturyHandler::CenturyRegister(_) => (bytes, Some(raw.century)),
+			CMOSCenturyHandler::CurrentYear(_) => (bytes, None),
+		}
+	}
+
+	/// Checks whether the second field is even, to blink a cursor or a clock colon at 1Hz.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 6 };
+	/// assert!(datetime.second_is_even());
+	/// assert!(!RTCDateTime { second: 7, ..datetime }.second_is_even());
+	/// ```
+	#[inline]
+	pub fn second_is_even(&self) -> bool { self.second % 2 == 0 }
+
+	/// Returns the month field as a [`Month`], or `None` if it is out of range.
+	///
+	/// [`Month`]: enum.Month.html
+	#[inline]
+	pub fn month_enum(&self) -> Option<Month> { Month::from_u8(self.month) }
+
+	/// Returns the English ordinal suffix of the day field ("st", "nd", "rd" or "th").
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let suffix = |day| RTCDateTime { year: 2019, month: 1, day, hour: 0, minute: 0, second: 0 }.day_ordinal_suffix();
+	/// assert_eq!([suffix(1), suffix(2), suffix(3), suffix(4)], ["st", "nd", "rd", "th"]);
+	/// assert_eq!([suffix(11), suffix(12), suffix(13)], ["th", "th", "th"]);
+	/// assert_eq!([suffix(21), suffix(22), suffix(23)], ["st", "nd", "rd"]);
+	/// ```
+	pub fn day_ordinal_suffix(&self) -> &'static str {
+		match (self.day % 10, self.day % 100) {
+			(_, 11..=13) => "th",
+			(1, _) => "st",
+			(2, _) => "nd",
+			(3, _) => "rd",
+			_ => "th",
+		}
+	}
+
+	/// Returns the meteorological season of the month in the given hemisphere.
+	/// In the northern hemisphere spring is March to May, summer June to August, autumn September to November and
+	/// winter December to February; the southern hemisphere is the other way around.
+	/// An invalid month is treated like December.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{Hemisphere, RTCDateTime, Season};
+	/// let in_month = |month| RTCDateTime { year: 2019, month, day: 1, hour: 0, minute: 0, second: 0 };
+	/// assert_eq!(in_month(4).season(Hemisphere::Northern), Season::Spring);
+	/// assert_eq!(in_month(7).season(Hemisphere::Northern), Season::Summer);
+	/// assert_eq!(in_month(10).season(Hemisphere::Northern), Season::Autumn);
+	/// assert_eq!(in_month(1).season(Hemisphere::Northern), Season::Winter);
+	/// assert_eq!(in_month(4).season(Hemisphere::Southern), Season::Autumn);
+	/// assert_eq!(in_month(7).season(Hemisphere::Southern), Season::Winter);
+	/// assert_eq!(in_month(10).season(Hemisphere::Southern), Season::Spring);
+	/// assert_eq!(in_month(1).season(Hemisphere::Southern), Season::Summer);
+	/// ```
+	pub fn season(&self, hemisphere: Hemisphere) -> Season {
+		let northern = match self.month {
+			3..=5 => Season::Spring,
+			6..=8 => Season::Summer,
+			9..=11 => Season::Autumn,
+			_ => Season::Winter,
+		};
+		match hemisphere {
+			Hemisphere::Northern => northern,
+			Hemisphere::Southern => match northern {
+				Season::Spring => Season::Autumn,
+				Season::Summer => Season::Winter,
+				Season::Autumn => Season::Spring,
+				Season::Winter => Season::Summer,
+			},
+		}
+	}
+
+	/// Returns the day of the week as a number, from 0 for Sunday to 6 for Saturday, computed from the date fields
+	/// with Sakamoto's algorithm. This is the numbering of [`Weekday`] (`weekday() as u8`), unlike the RTC weekday
+	/// register, which goes from 1 for Sunday to 7 for Saturday.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{RTCDateTime, Weekday};
+	/// let day_of_week = |year, month, day| RTCDateTime { year, month, day, hour: 0, minute: 0, second: 0 }.day_of_week();
+	/// // Saturday
+	/// assert_eq!(day_of_week(2000, 1, 1), 6);
+	/// // Thursday
+	/// assert_eq!(day_of_week(1970, 1, 1), 4);
+	/// // Sunday
+	/// assert_eq!(day_of_week(2019, 12, 29), 0);
+	/// // Thursday
+	/// assert_eq!(day_of_week(2024, 2, 29), 4);
+	/// assert_eq!(day_of_week(2024, 2, 29), Weekday::Thursday as u8);
+	/// ```
+	/// [`Weekday`]: enum.Weekday.html
+	pub fn day_of_week(&self) -> u8 {
+		const MONTH_OFFSETS: [u128; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+		// January and February count as the end of the previous year, shifted by a 400 year cycle to stay positive
+		let year = self.year as u128 + 400 - if self.month < 3 { 1 } else { 0 };
+		let month_offset = MONTH_OFFSETS[usize::from(self.month.clamp(1, 12)) - 1];
+		((year + year / 4 - year / 100 + year / 400 + month_offset + u128::from(self.day)) % 7) as u8
+	}
+
+	/// Returns the day of the week of the `RTCDateTime`, computed from the date fields.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{RTCDateTime, Weekday};
+	/// let datetime = RTCDateTime { year: 2000, month: 1, day: 1, hour: 0, minute: 0, second: 0 };
+	/// assert_eq!(datetime.weekday(), Weekday::Saturday);
+	/// ```
+	pub fn weekday(&self) -> Weekday {
+		// 0000-01-01 was a Saturday
+		match (RTCDateTime::days_before_year(self.year)
+			+ RTCDateTime::days_before_month(self.year, self.month)
+			+ i128::from(self.day)
+			+ 5) % 7
+		{
+			0 => Weekday::Sunday,
+			1 => Weekday::Monday,
+			2 => Weekday::Tuesday,
+			3 => Weekday::Wednesday,
+			4 => Weekday::Thursday,
+			5 => Weekday::Friday,
+			_ => Weekday::Saturday,
+		}
+	}
+
+	/// Returns the `n`th (starting at 1) occurrence of a weekday in a month, at midnight.
+	/// Returns `None` if there is no such day, like a 5th Friday in a month with only four, or for an invalid month.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{RTCDateTime, Weekday};
+	/// // The third Monday of January 2019
+	/// let datetime = RTCDateTime::nth_weekday_of_month(2019, 1, Weekday::Monday, 3).unwrap();
+	/// assert_eq!(datetime.as_tuple(), (2019, 1, 21, 0, 0, 0));
+	/// // January 2019 has four Mondays but five Thursdays
+	/// assert_eq!(RTCDateTime::nth_weekday_of_month(2019, 1, Weekday::Monday, 5), None);
+	/// assert_eq!(RTCDateTime::nth_weekday_of_month(2019, 1, Weekday::Thursday, 5).unwrap().day, 31);
+	/// assert_eq!(RTCDateTime::nth_weekday_of_month(2019, 1, Weekday::Tuesday, 1).unwrap().day, 1);
+	/// ```
+	pub fn nth_weekday_of_month(year: usize, month: u8, weekday: Weekday, n: u8) -> Option<Self> {
+		if n == 0 || !(1..=12).contains(&month) {
+			return None;
+		}
+		let first = Self { year, month, day: 1, hour: 0, minute: 0, second: 0 };
+		let offset = (weekday as u8 + 7 - first.weekday() as u8) % 7;
+		let day = 1 + u32::from(offset) + 7 * (u32::from(n) - 1);
+		if day > u32::from(RTCDateTime::days_by_month(year, month)) {
+			None
+		} else {
+			Some(Self { day: day as u8, ..first })
+		}
+	}
+
+	/// Returns the last occurrence of a weekday in a month, at midnight, like the last Friday of the month.
+	/// For an invalid month, the returned `RTCDateTime` is not valid either.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{RTCDateTime, Weekday};
+	/// let datetime = RTCDateTime::last_weekday_of_month(2019, 5, Weekday::Friday);
+	/// assert_eq!(datetime.as_tuple(), (2019, 5, 31, 0, 0, 0));
+	/// assert_eq!(RTCDateTime::last_weekday_of_month(2019, 5, Weekday::Monday).day, 27);
+	/// ```
+	pub fn last_weekday_of_month(year: usize, month: u8, weekday: Weekday) -> Self {
+		let last = Self { year, month, day: RTCDateTime::days_by_month(year, month), hour: 0, minute: 0, second: 0 };
+		let offset = (last.weekday() as u8 + 7 - weekday as u8) % 7;
+		Self { day: last.day.saturating_sub(offset), ..last }
+	}
+
+	/// Returns an iterator over every day from `start` to `end` (both inclusive), keeping the time of `start`.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let start = RTCDateTime { year: 2019, month: 2, day: 27, hour: 12, minute: 0, second: 0 };
+	/// let end = RTCDateTime { year: 2019, month: 3, day: 2, hour: 12, minute: 0, second: 0 };
+	/// assert_eq!(RTCDateTime::iter_days(start, end).count(), 4);
+	/// ```
+	pub fn iter_days(start: Self, end: Self) -> DayIter { DayIter { next: Some(start), end } }
+
+	/// Returns an iterator over every day from `start` to `end` (both inclusive) falling on the given weekday.
+	///
+	/// # Examples
+	/// Count the Mondays in January 2019
+	/// ```rust
+	/// # use cmos::{RTCDateTime, Weekday};
+	/// let start = RTCDateTime { year: 2019, month: 1, day: 1, hour: 0, minute: 0, second: 0 };
+	/// let end = RTCDateTime { year: 2019, month: 1, day: 31, hour: 0, minute: 0, second: 0 };
+	/// assert_eq!(RTCDateTime::weekdays_between(start, end, Weekday::Monday).count(), 4);
+	/// assert_eq!(RTCDateTime::weekdays_between(start, end, Weekday::Thursday).count(), 5);
+	/// ```
+	pub fn weekdays_between(start: Self, end: Self, weekday: Weekday) -> impl Iterator<Item = Self> {
+		RTCDateTime::iter_days(start, end).filter(move |datetime| datetime.weekday() == weekday)
+	}
+
+	/// Returns the start of the week, 00:00:00 on the most recent `first_day` at or before the `RTCDateTime`.
+	/// Saturates at [`MIN`](constant.MIN.html).
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{RTCDateTime, Weekday};
+	/// // Wednesday
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(datetime.start_of_week(Weekday::Monday).as_tuple(), (2018, 12, 31, 0, 0, 0));
+	/// assert_eq!(datetime.start_of_week(Weekday::Sunday).as_tuple(), (2018, 12, 30, 0, 0, 0));
+	/// assert_eq!(datetime.start_of_week(Weekday::Wednesday).as_tuple(), (2019, 1, 2, 0, 0, 0));
+	/// assert_eq!(datetime.start_of_week(Weekday::Thursday).as_tuple(), (2018, 12, 27, 0, 0, 0));
+	/// ```
+	pub fn start_of_week(&self, first_day: Weekday) -> Self {
+		let days_back = (self.weekday() as u8 + 7 - first_day as u8) % 7;
+		let day_start = RTCDateTime { hour: 0, minute: 0, second: 0, ..*self }.seconds_since_year_zero();
+		RTCDateTime::from_seconds_since_year_zero(day_start - i128::from(days_back) * 86400)
+	}
+
+	/// Checks whether the `RTCDateTime` matches a cron like schedule, where `None` matches any value.
+	/// The seconds are ignored.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{RTCDateTime, Weekday};
+	/// // Monday
+	/// let datetime = RTCDateTime { year: 2019, month: 6, day: 3, hour: 9, minute: 0, second: 30 };
+	/// // Every day at 09:00
+	/// assert!(datetime.matches_schedule(Some(0), Some(9), None, None, None));
+	/// assert!(!RTCDateTime { minute: 1, ..datetime }.matches_schedule(Some(0), Some(9), None, None, None));
+	/// // Every minute of Mondays in June
+	/// assert!(datetime.matches_schedule(None, None, None, Some(6), Some(Weekday::Monday)));
+	/// assert!(!RTCDateTime { day: 4, ..datetime }.matches_schedule(None, None, None, Some(6), Some(Weekday::Monday)));
+	/// ```
+	pub fn matches_schedule(
+		&self,
+		minute: Option<u8>,
+		hour: Option<u8>,
+		day: Option<u8>,
+		month: Option<u8>,
+		weekday: Option<Weekday>,
+	) -> bool {
+		let schedule = Schedule { minute, hour, day, month, weekday };
+		schedule.matches_date(self) && schedule.matches_time(self.hour, self.minute)
+	}
+
+	/// Returns the first date time strictly after `start` that matches the schedule, at 0 seconds, or `None` if the
+	/// schedule never matches (like February 30th, or an hour of 24).
+	/// Every combination of day, month and weekday comes back within a 400 year cycle of the Gregorian calendar, so
+	/// the search is bounded.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{RTCDateTime, Schedule, Weekday};
+	/// let start = RTCDateTime { year: 2019, month: 1, day: 2, hour: 9, minute: 0, second: 0 };
+	/// let every_day_at_9 = Schedule { minute: Some(0), hour: Some(9), ..Schedule::default() };
+	/// let next = RTCDateTime::next_match_after(start, every_day_at_9).unwrap();
+	/// assert_eq!(next.as_tuple(), (2019, 1, 3, 9, 0, 0));
+	/// let next = RTCDateTime::next_match_after(RTCDateTime { hour: 8, ..start }, every_day_at_9).unwrap();
+	/// assert_eq!(next.as_tuple(), (2019, 1, 2, 9, 0, 0));
+	///
+	/// let mondays_in_june = Schedule { month: Some(6), weekday: Some(Weekday::Monday), ..Schedule::default() };
+	/// let next = RTCDateTime::next_match_after(start, mondays_in_june).unwrap();
+	/// assert_eq!(next.as_tuple(), (2019, 6, 3, 0, 0, 0));
+	/// let next = RTCDateTime::next_match_after(RTCDateTime { hour: 23, minute: 59, ..next }, mondays_in_june).unwrap();
+	/// assert_eq!(next.as_tuple(), (2019, 6, 10, 0, 0, 0));
+	///
+	/// // Friday the 13th
+	/// let friday_13th = Schedule { day: Some(13), weekday: Some(Weekday::Friday), ..Schedule::default() };
+	/// assert_eq!(RTCDateTime::next_match_after(start, friday_13th).unwrap().as_tuple(), (2019, 9, 13, 0, 0, 0));
+	///
+	/// let february_30th = Schedule { day: Some(30), month: Some(2), ..Schedule::default() };
+	/// assert_eq!(RTCDateTime::next_match_after(start, february_30th), None);
+	/// ```
+	pub fn next_match_after(start: Self, schedule: Schedule) -> Option<Self> {
+		if schedule.minute.unwrap_or(0) >= 60 || schedule.hour.unwrap_or(0) >= 24 {
+			return None;
+		}
+
+		// Searches from the minute after `start`, the later days starting at midnight
+		let mut day = Self { second: 0, ..start };
+		let mut first_minute = u16::from(start.hour) * 60 + u16::from(start.minute) + 1;
+		for _ in 0..=146_097 {
+			if schedule.matches_date(&day) {
+				let time = (first_minute..24 * 60).find(|&time| schedule.matches_time((time / 60) as u8, (time % 60) as u8));
+				if let Some(time) = time {
+					return Some(Self { hour: (time / 60) as u8, minute: (time % 60) as u8, ..day });
+				}
+			}
+			day = Self { hour: 0, minute: 0, ..day.next_day()? };
+			first_minute = 0;
+		}
+		None
+	}
+
+	/// Checks whether the `RTCDateTime` falls in the same ISO 8601 week as `other`, comparing the ISO year and week
+	/// number. ISO weeks start on Monday and can span two months, or two years.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// // Monday 2019-07-29 to Sunday 2019-08-04
+	/// let monday = RTCDateTime { year: 2019, month: 7, day: 29, hour: 0, minute: 0, second: 0 };
+	/// let sunday = RTCDateTime { month: 8, day: 4, hour: 23, ..monday };
+	/// assert!(monday.same_iso_week(&sunday));
+	/// assert!(!sunday.same_iso_week(&RTCDateTime { day: 5, ..sunday }));
+	/// assert!(!monday.same_iso_week(&RTCDateTime { day: 28, ..monday }));
+	/// // 2019-12-30 is in the first week of 2020
+	/// let new_year = RTCDateTime { year: 2020, month: 1, day: 1, ..monday };
+	/// assert!(new_year.same_iso_week(&RTCDateTime { year: 2019, month: 12, day: 30, ..monday }));
+	/// // 2021-01-03 is in the 53rd week of 2020
+	/// let new_year = RTCDateTime { year: 2021, day: 3, ..new_year };
+	/// assert!(new_year.same_iso_week(&RTCDateTime { year: 2020, month: 12, day: 31, ..monday }));
+	/// assert!(!new_year.same_iso_week(&RTCDateTime { day: 4, ..new_year }));
+	/// ```
+	pub fn same_iso_week(&self, other: &Self) -> bool { self.iso_week() == other.iso_week() }
+
+	/// Returns the ISO 8601 year and week number (1 to 53) of the date. Weeks start on Monday, and week 1 is the week
+	/// with the first Thursday of the year, so the first and last days of a year can belong to the neighbouring
+	/// ISO year.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let datetime = RTCDateTime { year: 2019, month: 6, day: 12, hour: 3, minute: 4, second: 5 };
+	/// assert_eq!(datetime.iso_week(), (2019, 24));
+	/// // 2021-01-01 is a Friday, in the last week of 2020
+	/// assert_eq!(RTCDateTime { year: 2021, month: 1, day: 1, ..datetime }.iso_week(), (2020, 53));
+	/// assert_eq!(RTCDateTime { year: 2021, month: 1, day: 4, ..datetime }.iso_week(), (2021, 1));
+	/// // 2019-12-30 is a Monday, in the first week of 2020
+	/// assert_eq!(RTCDateTime { year: 2019, month: 12, day: 29, ..datetime }.iso_week(), (2019, 52));
+	/// assert_eq!(RTCDateTime { year: 2019, month: 12, day: 30, ..datetime }.iso_week(), (2020, 1));
+	/// assert_eq!(RTCDateTime { year: 2015, month: 12, day: 31, ..datetime }.iso_week(), (2015, 53));
+	/// ```
+	pub fn iso_week(&self) -> (usize, u8) {
+		// Week of the given day of the year (from 1), knowing its weekday (from 1 for Monday to 7 for Sunday)
+		let week = |ordinal: i128, iso_weekday: i128| (ordinal - iso_weekday + 10) / 7;
+		let iso_weekday = |datetime: &Self| i128::from((datetime.day_of_week() + 6) % 7 + 1);
+		// December 28th is always in the last week of its ISO year
+		let weeks_in_year = |year: usize| {
+			let december_28 = Self { year, month: 12, day: 28, hour: 0, minute: 0, second: 0 };
+			week(i128::from(december_28.ordinal()), iso_weekday(&december_28))
+		};
+
+		let week = week(i128::from(self.ordinal()), iso_weekday(self));
+		if week < 1 {
+			let year = self.year.saturating_sub(1);
+			(year, weeks_in_year(year) as u8)
+		} else if week > weeks_in_year(self.year) {
+			(self.year.saturating_add(1), 1)
+		} else {
+			(self.year, week as u8)
+		}
+	}
+
+	/// Checks whether `year` is a leap year in the (proleptic) Gregorian calendar: a multiple of 4, except
+	/// multiples of 100 that aren't multiples of 400.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// assert!(!RTCDateTime::is_leap_year(1900));
+	/// assert!(RTCDateTime::is_leap_year(2000));
+	/// assert!(RTCDateTime::is_leap_year(2004));
+	/// assert!(!RTCDateTime::is_leap_year(2019));
+	/// assert!(!RTCDateTime::is_leap_year(2100));
+	/// ```
+	pub fn is_leap_year(year: usize) -> bool { (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 }
+
+	/// Returns the maximal number of days given a month and a year.
+	#[doc(hidden)]
+	fn days_by_month(year: usize, month: u8) -> u8 {
+		match month {
+			1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+			4 | 6 | 9 | 11 => 30,
+			2 => {
+				if RTCDateTime::is_leap_year(year) {
+					29
+				} else {
+					28
+				}
+			},
+			_ => 0,
+		}
+	}
+
+	/// Parses the decimal fields of `s`, separated by `separator`, into `fields` and returns how many there were.
+	/// Fails with `ParseError::Separator` at a separator past the fields `fields` holds or right after another one,
+	/// `ParseError::Length` if `s` is empty or ends with a separator, `ParseError::NotADigit` at a byte that isn't a
+	/// decimal digit, and `ParseError::OutOfRange` with the field from `names` if a field overflows a `usize`.
+	#[doc(hidden)]
+	fn parse_fields(s: &str, separator: char, fields: &mut [usize], names: [RtcField; 3]) -> Result<usize, ParseError> {
+		let mut count = 0;
+		let mut start = 0;
+		for part in s.split(separator) {
+			let field = fields.get_mut(count).ok_or_else(|| ParseError::Separator(start - separator.len_utf8()))?;
+			if part.is_empty() {
+				return Err(if start == s.len() { ParseError::Length } else { ParseError::Separator(start) });
+			}
+			let mut value = 0usize;
+			for (index, byte) in part.bytes().enumerate() {
+				if !byte.is_ascii_digit() {
+					return Err(ParseError::NotADigit(start + index));
+				}
+				value = value
+					.checked_mul(10)
+					.and_then(|value| value.checked_add(usize::from(byte - b'0')))
+					.ok_or(ParseError::OutOfRange(names[count]))?;
+			}
+			*field = value;
+			start += part.len() + separator.len_utf8();
+			count += 1;
+		}
+		Ok(count)
+	}
+
+	/// Adds `n` to a field whose values go from `first` to `first + count - 1`, wrapping around.
+	/// Returns the new value and whether it wrapped. A field with no values (`count == 0`) is returned unchanged.
+	#[doc(hidden)]
+	fn wrapping_add_field(value: u8, n: u8, first: u8, count: u8) -> (u8, bool) {
+		if count == 0 {
+			return (value, false);
+		}
+		let total = u16::from(value.saturating_sub(first)) + u16::from(n);
+		((total % u16::from(count)) as u8 + first, total >= u16::from(count))
+	}
+
+	/// Encodes the date time into raw time registers in the given format, century included.
+	/// The `register_b` field only holds the format bits.
+	#[doc(hidden)]
+	pub(crate) fn encode(&self, format: RtcFormat) -> RawRtcRegisters {
+		let encode = |value: u8| if format.binary { value } else { ((value / 10) << 4) | (value % 10) };
+		let hour = if format.hour_24 {
+			encode(self.hour)
+		} else {
+			// 12 hour clock: 12 AM is midnight, 12 PM is noon, and bit 7 is set for PM
+			let pm = if self.hour >= 12 { 0x80 } else { 0 };
+			encode(match self.hour % 12 {
+				0 => 12,
+				hour => hour,
+			}) | pm
+		};
+		RawRtcRegisters {
+			second: encode(self.second),
+			minute: encode(self.minute),
+			hour,
+			weekday: self.weekday() as u8 + 1,
+			day: encode(self.day),
+			month: encode(self.month),
+			year: encode((self.year % 100) as u8),
+			register_b: format.register_b_bits(),
+			century: encode((self.year / 100 % 100) as u8),
+		}
+	}
+
+	/// Returns the number of days from `0000-01-01` to the first day of the given year (proleptic Gregorian calendar).
+	#[doc(hidden)]
+	fn days_before_year(year: usize) -> i128 {
+		let year = year as i128;
+		// Year 0 is a leap year, so the leap years before `year` are counted from 0 to `year - 1`
+		365 * year + (year + 3) / 4 - (year + 99) / 100 + (year + 399) / 400
+	}
+
+	/// Returns the number of days from the first day of the year to the first day of the given month.
+	#[doc(hidden)]
+	fn days_before_month(year: usize, month: u8) -> i128 {
+		(1..month).map(|m| i128::from(RTCDateTime::days_by_month(year, m))).sum()
+	}
+
+	/// Returns the number of days from `0000-01-01` to the date, ignoring the time.
+	#[doc(hidden)]
+	fn days_since_year_zero(&self) -> i128 {
+		RTCDateTime::days_before_year(self.year)
+			+ RTCDateTime::days_before_month(self.year, self.month)
+			+ i128::from(self.day)
+			- 1
+	}
+
+	/// Returns the number of seconds from `0000-01-01T00:00:00` to this date time.
+	#[doc(hidden)]
+	pub(crate) fn seconds_since_year_zero(&self) -> i128 {
+		self.days_since_year_zero() * 86400
+			+ i128::from(self.hour) * 3600
+			+ i128::from(self.minute) * 60
+			+ i128::from(self.second)
+	}
+
+	/// Creates a `RTCDateTime` from a number of seconds since `0000-01-01T00:00:00`, or `None` if it is out of the
+	/// range from [`MIN`] to [`MAX`].
+	///
+	/// [`MIN`]: constant.MIN.html
+	/// [`MAX`]: constant.MAX.html
+	#[doc(hidden)]
+	fn checked_from_seconds_since_year_zero(seconds: i128) -> Option<Self> {
+		if seconds < 0 || seconds > MAX.seconds_since_year_zero() {
+			None
+		} else {
+			Some(RTCDateTime::from_seconds_since_year_zero(seconds))
+		}
+	}
+
+	/// Creates a `RTCDateTime` from a number of seconds since `0000-01-01T00:00:00`, saturating at [`MIN`] and
+	/// [`MAX`].
+	///
+	/// [`MIN`]: constant.MIN.html
+	/// [`MAX`]: constant.MAX.html
+	#[doc(hidden)]
+	pub(crate) fn from_seconds_since_year_zero(seconds: i128) -> Self {
+		if seconds < 0 {
+			return MIN;
+		}
+		let mut days = seconds / 86400;
+		let time = seconds % 86400;
+
+		// Estimate the year from the average year length over a 400 year cycle, then correct it
+		let mut year = days * 400 / 146_097;
+		if year > usize::MAX as i128 {
+			return MAX;
+		}
+		while year > 0 && RTCDateTime::days_before_year(year as usize) > days {
+			year -= 1;
+		}
+		while year < usize::MAX as i128 && RTCDateTime::days_before_year(year as usize + 1) <= days {
+			year += 1;
+		}
+		let year = year as usize;
+		days -= RTCDateTime::days_before_year(year);
+		if days >= 366 {
+			// Past the last day of year `usize::MAX`
+			return MAX;
+		}
+
+		let mut month = 1;
+		while days >= i128::from(RTCDateTime::days_by_month(year, month)) {
+			days -= i128::from(RTCDateTime::days_by_month(year, month));
+			month += 1;
+		}
+		Self {
+			year,
+			month,
+			day: days as u8 + 1,
+			hour: (time / 3600) as u8,
+			minute: (time / 60 % 60) as u8,
+			second: (time % 60) as u8,
+		}
+	}
+
+	/// Returns the same time on the following day, or `None` if the year would overflow.
+	#[doc(hidden)]
+	fn next_day(&self) -> Option<Self> {
+		if self.day < RTCDateTime::days_by_month(self.year, self.month) {
+			Some(Self { day: self.day + 1, ..*self })
+		} else if self.month < 12 {
+			Some(Self { month: self.month + 1, day: 1, ..*self })
+		} else {
+			self.year.checked_add(1).map(|year| Self { year, month: 1, day: 1, ..*self })
+		}
+	}
+}
+
+/// Cron like schedule matched by [`RTCDateTime::matches_schedule`] and [`RTCDateTime::next_match_after`].
+/// Each field restricts the date times matching to one value, `None` matching any value. The day of the month and
+/// the weekday both have to match when both are set.
+///
+/// [`RTCDateTime::matches_schedule`]: struct.RTCDateTime.html#method.matches_schedule
+/// [`RTCDateTime::next_match_after`]: struct.RTCDateTime.html#method.next_match_after
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Schedule {
+	pub minute: Option<u8>,
+	pub hour: Option<u8>,
+	pub day: Option<u8>,
+	pub month: Option<u8>,
+	pub weekday: Option<Weekday>,
+}
+
+impl Schedule {
+	/// Checks whether the date fields of `datetime` match
+	fn matches_date(&self, datetime: &RTCDateTime) -> bool {
+		(self.day.is_none() || self.day == Some(datetime.day))
+			&& (self.month.is_none() || self.month == Some(datetime.month))
+			&& (self.weekday.is_none() || self.weekday == Some(datetime.weekday()))
+	}
+
+	/// Checks whether the given hour and minute match
+	fn matches_time(&self, hour: u8, minute: u8) -> bool {
+		(self.hour.is_none() || self.hour == Some(hour)) && (self.minute.is_none() || self.minute == Some(minute))
+	}
+}
+
+/// Iterator over consecutive days, created by [`RTCDateTime::iter_days`]
+///
+/// [`RTCDateTime::iter_days`]: struct.RTCDateTime.html#method.iter_days
+#[derive(Debug, Clone)]
+pub struct DayIter {
+	next: Option<RTCDateTime>,
+	end: RTCDateTime,
+}
+
+impl Iterator for DayIter {
+	type Item = RTCDateTime;
+
+	fn next(&mut self) -> Option<RTCDateTime> {
+		let current = self.next.filter(|datetime| *datetime <= self.end)?;
+		self.next = current.next_day();
+		Some(current)
+	}
+}
+
+/// Sorts a slice of `RTCDateTime` in chronological order, in place.
+/// The sort is stable. As `no_std` has no allocator to back `slice::sort`, this is an insertion sort, which is fast
+/// on small or nearly sorted slices; use [`sort_chronological_unstable`] for large slices.
+///
+/// # Examples
+/// ```rust
+/// # use cmos::{sort_chronological, is_sorted_chronological, RTCDateTime};
+/// let at = |hour| RTCDateTime { year: 2019, month: 1, day: 2, hour, minute: 0, second: 0 };
+/// let mut datetimes = [at(3), at(1), at(2)];
+/// sort_chronological(&mut datetimes);
+/// assert_eq!(datetimes, [at(1), at(2), at(3)]);
+/// assert!(is_sorted_chronological(&datetimes));
+/// ```
+/// [`sort_chronological_unstable`]: fn.sort_chronological_unstable.html
+pub fn sort_chronological(slice: &mut [RTCDateTime]) {
+	for i in 1..slice.len() {
+		let mut j = i;
+		while j > 0 && slice[j - 1] > slice[j] {
+			slice.swap(j - 1, j);
+			j -= 1;
+		}
+	}
+}
+
+/// Sorts a slice of `RTCDateTime` in chronological order, in place, without preserving the order of equal elements.
+///
+/// # Examples
+/// ```rust
+/// # use cmos::{sort_chronological_unstable, is_sorted_chronological, RTCDateTime};
+/// let at = |hour| RTCDateTime { year: 2019, month: 1, day: 2, hour, minute: 0, second: 0 };
+/// let mut datetimes = [at(3), at(1), at(2)];
+/// sort_chronological_unstable(&mut datetimes);
+/// assert!(is_sorted_chronological(&datetimes));
+/// ```
+pub fn sort_chronological_unstable(slice: &mut [RTCDateTime]) { slice.sort_unstable(); }
+
+/// Checks whether a slice of `RTCDateTime` is in chronological order.
+///
+/// # Examples
+/// ```rust
+/// # use cmos::{is_sorted_chronological, RTCDateTime};
+/// let at = |hour| RTCDateTime { year: 2019, month: 1, day: 2, hour, minute: 0, second: 0 };
+/// assert!(is_sorted_chronological(&[at(1), at(1), at(2)]));
+/// assert!(!is_sorted_chronological(&[at(2), at(1)]));
+/// ```
+pub fn is_sorted_chronological(slice: &[RTCDateTime]) -> bool { slice.windows(2).all(|pair| pair[0] <= pair[1]) }
+
+/// A span of time, to add to or subtract from a `RTCDateTime`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RtcDuration {
+	pub days: u64,
+	pub hours: u8,
+	pub minutes: u8,
+	pub seconds: u8,
+}
+
+impl RtcDuration {
+	/// Creates a normalized span from a number of seconds: the hours, minutes and seconds are below 24, 60 and 60.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RtcDuration;
+	/// let duration = RtcDuration::from_seconds(2 * 86400 + 3 * 3600 + 4 * 60 + 5);
+	/// assert_eq!(duration, RtcDuration { days: 2, hours: 3, minutes: 4, seconds: 5 });
+	/// assert_eq!(duration.as_seconds(), 183_845);
+	/// ```
+	pub fn from_seconds(seconds: u64) -> RtcDuration {
+		RtcDuration {
+			days: seconds / 86400,
+			hours: (seconds % 86400 / 3600) as u8,
+			minutes: (seconds % 3600 / 60) as u8,
+			seconds: (seconds % 60) as u8,
+		}
+	}
+
+	/// Returns the total number of seconds in the span, saturating at `u64::MAX`
+	pub fn as_seconds(&self) -> u64 { self.total_seconds().min(i128::from(u64::MAX)) as u64 }
+
+	/// Returns the total number of seconds in the span
+	fn total_seconds(&self) -> i128 {
+		i128::from(self.days) * 86400
+			+ i128::from(self.hours) * 3600
+			+ i128::from(self.minutes) * 60
+			+ i128::from(self.seconds)
+	}
+}
+
+impl Display for RtcDuration {
+	/// Prints the span as `Dd HH:MM:SS`, the days followed by the time of day, normalized.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RtcDuration;
+	/// assert_eq!(format!("{}", RtcDuration::from_seconds(3 * 3600 + 4 * 60 + 5)), "0d 03:04:05");
+	/// assert_eq!(format!("{}", RtcDuration { days: 2, hours: 25, minutes: 0, seconds: 0 }), "3d 01:00:00");
+	/// ```
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		let duration = RtcDuration::from_seconds(self.as_seconds());
+		write!(f, "{}d {:02}:{:02}:{:02}", duration.days, duration.hours, duration.minutes, duration.seconds)
+	}
+}
+
+impl Add<RtcDuration> for RTCDateTime {
+	type Output = RTCDateTime;
+
+	/// Moves the date time forward by the duration, carrying into minutes, hours, days, months and years.
+	/// Saturates at [`MAX`](constant.MAX.html).
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{RTCDateTime, RtcDuration, MAX};
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 30, hour: 22, minute: 0, second: 0 };
+	/// let duration = RtcDuration { days: 2, hours: 3, minutes: 0, seconds: 0 };
+	/// assert_eq!((datetime + duration).as_tuple(), (2019, 2, 2, 1, 0, 0));
+	///
+	/// let late = RTCDateTime { year: 2019, month: 12, day: 31, hour: 23, minute: 30, second: 0 };
+	/// assert_eq!((late + RtcDuration::from_seconds(3600)).as_tuple(), (2020, 1, 1, 0, 30, 0));
+	/// let mut datetime = late;
+	/// datetime += RtcDuration::from_seconds(1800);
+	/// assert_eq!(datetime.as_tuple(), (2020, 1, 1, 0, 0, 0));
+	/// assert_eq!(MAX + RtcDuration::from_seconds(1), MAX);
+	/// ```
+	fn add(self, duration: RtcDuration) -> RTCDateTime {
+		RTCDateTime::from_seconds_since_year_zero(self.seconds_since_year_zero() + duration.total_seconds())
+	}
+}
+
+impl AddAssign<RtcDuration> for RTCDateTime {
+	fn add_assign(&mut self, duration: RtcDuration) { *self = *self + duration; }
+}
+
+impl Sub<RtcDuration> for RTCDateTime {
+	type Output = RTCDateTime;
+
+	/// Moves the date time backward by the duration, borrowing from minutes, hours, days, months and years.
+	/// Saturates at [`MIN`](constant.MIN.html).
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{RTCDateTime, RtcDuration, MIN};
+	/// let datetime = RTCDateTime { year: 2020, month: 3, day: 1, hour: 1, minute: 0, second: 0 };
+	/// let duration = RtcDuration { days: 1, hours: 2, minutes: 0, seconds: 0 };
+	/// assert_eq!((datetime - duration).as_tuple(), (2020, 2, 28, 23, 0, 0));
+	/// assert_eq!(MIN - duration, MIN);
+	///
+	/// let first = RTCDateTime { year: 2019, month: 5, day: 1, hour: 12, minute: 0, second: 0 };
+	/// assert_eq!((first - RtcDuration::from_seconds(86400)).as_tuple(), (2019, 4, 30, 12, 0, 0));
+	/// let mut datetime = first;
+	/// datetime -= RtcDuration::from_seconds(86400 * 30);
+	/// assert_eq!(datetime.as_tuple(), (2019, 4, 1, 12, 0, 0));
+	/// ```
+	fn sub(self, duration: RtcDuration) -> RTCDateTime {
+		RTCDateTime::from_seconds_since_year_zero(self.seconds_since_year_zero() - duration.total_seconds())
+	}
+}
+
+impl SubAssign<RtcDuration> for RTCDateTime {
+	fn sub_assign(&mut self, duration: RtcDuration) { *self = *self - duration; }
+}
+
+impl Add for RTCDateTime {
+	type Output = RTCDateTime;
+
+	/// Adds a span, whose fields are read as a number of years, months, days, hours, minutes and seconds, to the
+	/// date time.
+	/// The years and months are added first, in calendar terms, keeping the day of the month unless the new month is
+	/// shorter: January 31st plus 1 month is the last day of February. The days, hours, minutes and seconds are
+	/// then added with every carry propagated, taking the length of each month and leap years into account.
+	/// Saturates at [`MAX`](constant.MAX.html).
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::RTCDateTime;
+	/// let span = |year, month, day, hour, minute, second| RTCDateTime { year, month, day, hour, minute, second };
+	/// let datetime = RTCDateTime { year: 2019, month: 12, day: 31, hour: 23, minute: 59, second: 59 };
+	/// assert_eq!((datetime + span(0, 0, 0, 0, 0, 1)).as_tuple(), (2020, 1, 1, 0, 0, 0));
+	/// assert_eq!((datetime + span(0, 0, 0, 0, 0, 90)).as_tuple(), (2020, 1, 1, 0, 1, 29));
+	/// assert_eq!((datetime + span(0, 2, 0, 0, 0, 0)).as_tuple(), (2020, 2, 29, 23, 59, 59));
+	/// assert_eq!((datetime + span(1, 2, 0, 0, 0, 0)).as_tuple(), (2021, 2, 28, 23, 59, 59));
+	/// assert_eq!((datetime + span(0, 2, 1, 0, 0, 1)).as_tuple(), (2020, 3, 2, 0, 0, 0));
+	///
+	/// let mut datetime = datetime;
+	/// datetime += span(0, 0, 60, 0, 0, 0);
+	/// assert_eq!(datetime.as_tuple(), (2020, 2, 29, 23, 59, 59));
+	/// ```
+	fn add(self, span: RTCDateTime) -> RTCDateTime {
+		let months = (self.year as i128 + span.year as i128) * 12
+			+ i128::from(self.month.saturating_sub(1))
+			+ i128::from(span.month);
+		if months / 12 > usize::MAX as i128 {
+			return MAX;
+		}
+		let year = (months / 12) as usize;
+		let month = (months % 12) as u8 + 1;
+		let day = self.day.min(RTCDateTime::days_by_month(year, month));
+		let seconds = RTCDateTime { year, month, day, ..self }.seconds_since_year_zero()
+			+ i128::from(span.day) * 86400
+			+ i128::from(span.hour) * 3600
+			+ i128::from(span.minute) * 60
+			+ i128::from(span.second);
+		RTCDateTime::from_seconds_since_year_zero(seconds)
+	}
+}
+
+impl AddAssign for RTCDateTime {
+	fn add_assign(&mut self, span: RTCDateTime) { *self = *self + span; }
+}
+
+impl Sub for RTCDateTime {
+	type Output = RTCDateTime;
+
+	/// Returns the time elapsed from `other` to `self`, as the date time that many seconds after
+	/// `0000-01-01T00:00:00`: one day and two hours elapsed is `0000-01-02T02:00:00`. Use
+	/// [`difference_in_seconds`] to get a plain number of seconds.
+	/// Clamps to [`MIN`] if `other` is later than `self`.
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{RTCDateTime, MIN};
+	/// let before = RTCDateTime { year: 2020, month: 2, day: 28, hour: 12, minute: 0, second: 0 };
+	/// let after = RTCDateTime { year: 2020, month: 3, day: 1, hour: 13, minute: 2, second: 3 };
+	/// // 2020-02-29 is counted
+	/// assert_eq!((after - before).as_tuple(), (0, 1, 3, 1, 2, 3));
+	/// assert_eq!(before - after, MIN);
+	/// assert_eq!(after - after, MIN);
+	///
+	/// let mut elapsed = RTCDateTime { year: 2021, ..after };
+	/// elapsed -= before;
+	/// // Year 0 is a leap year, so 367 days later is 0001-01-02
+	/// assert_eq!(elapsed.as_tuple(), (1, 1, 2, 1, 2, 3));
+	/// assert_eq!(RTCDateTime { year: 2021, ..after }.difference_in_seconds(&before), (367 * 24 + 1) * 3600 + 123);
+	/// ```
+	/// [`difference_in_seconds`]: struct.RTCDateTime.html#method.difference_in_seconds
+	/// [`MIN`]: constant.MIN.html
+	fn sub(self, other: RTCDateTime) -> RTCDateTime {
+		RTCDateTime::from_seconds_since_year_zero(self.seconds_since_year_zero() - other.seconds_since_year_zero())
+	}
+}
+
+impl SubAssign for RTCDateTime {
+	fn sub_assign(&mut self, other: RTCDateTime) { *self = *self - other; }
+}
+
+impl From<RTCDateTime> for [u8; 7] {
+	/// Packs the date time with [`RTCDateTime::to_bytes`]
+	///
+	/// [`RTCDateTime::to_bytes`]: struct.RTCDateTime.html#method.to_bytes
+	fn from(datetime: RTCDateTime) -> [u8; 7] { datetime.to_bytes() }
+}
+
+/// C compatible version of `RTCDateTime`, to pass across an FFI boundary.
+/// The layout is the one of the C struct
+/// ```c
+/// struct rtc_date_time {
+///     uint16_t year;
+///     uint8_t month;
+///     uint8_t day;
+///     uint8_t hour;
+///     uint8_t minute;
+///     uint8_t second;
+/// };
+/// ```
+///
+/// # Examples
+/// ```rust
+/// # use cmos::{CRTCDateTime, RTCDateTime};
+/// # use core::convert::TryFrom;
+/// assert_eq!(core::mem::size_of::<CRTCDateTime>(), 8);
+/// assert_eq!(core::mem::align_of::<CRTCDateTime>(), 2);
+///
+/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+/// let c_datetime = CRTCDateTime::try_from(datetime).unwrap();
+/// assert_eq!(RTCDateTime::from(c_datetime), datetime);
+/// assert!(CRTCDateTime::try_from(RTCDateTime { year: 65536, ..datetime }).is_err());
+/// ```
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CRTCDateTime {
+	pub year: u16,
+	pub month: u8,
+	pub day: u8,
+	pub hour: u8,
+	pub minute: u8,
+	pub second: u8,
+}
+
+impl From<CRTCDateTime> for RTCDateTime {
+	fn from(datetime: CRTCDateTime) -> RTCDateTime {
+		RTCDateTime {
+			year: datetime.year as usize,
+			month: datetime.month,
+			day: datetime.day,
+			hour: datetime.hour,
+			minute: datetime.minute,
+			second: datetime.second,
+		}
+	}
+}
+
+impl TryFrom<RTCDateTime> for CRTCDateTime {
+	type Error = CmosError;
+
+	/// Fails with `CmosError::InvalidField(RtcField::Year)` if the year doesn't fit in 16 bits
+	fn try_from(datetime: RTCDateTime) -> Result<CRTCDateTime, CmosError> {
+		if datetime.year > usize::from(u16::MAX) {
+			return Err(CmosError::InvalidField(RtcField::Year));
+		}
+		Ok(CRTCDateTime {
+			year: datetime.year as u16,
+			month: datetime.month,
+			day: datetime.day,
+			hour: datetime.hour,
+			minute: datetime.minute,
+			second: datetime.second,
+		})
+	}
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<RTCDateTime> for chrono::NaiveDateTime {
+	type Error = CmosError;
+
+	/// Fails with `CmosError::InvalidField` and the first field out of range, or with `RtcField::Year` if the year
+	/// is beyond chrono's range
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{CmosError, RTCDateTime, RtcField};
+	/// # use core::convert::TryFrom;
+	/// use chrono::NaiveDateTime;
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// let naive = NaiveDateTime::try_from(datetime).unwrap();
+	/// assert_eq!(naive.to_string(), "2019-01-02 03:04:05");
+	/// assert_eq!(RTCDateTime::try_from(naive), Ok(datetime));
+	///
+	/// let feb_30 = RTCDateTime { month: 2, day: 30, ..datetime };
+	/// assert_eq!(NaiveDateTime::try_from(feb_30), Err(CmosError::InvalidField(RtcField::Day)));
+	/// let far = RTCDateTime { year: 1_000_000, ..datetime };
+	/// assert_eq!(NaiveDateTime::try_from(far), Err(CmosError::InvalidField(RtcField::Year)));
+	/// ```
+	fn try_from(datetime: RTCDateTime) -> Result<chrono::NaiveDateTime, CmosError> {
+		if let Some(field) = datetime.invalid_field() {
+			return Err(CmosError::InvalidField(field));
+		}
+		let year = i32::try_from(datetime.year).map_err(|_| CmosError::InvalidField(RtcField::Year))?;
+		let date = chrono::NaiveDate::from_ymd_opt(year, u32::from(datetime.month), u32::from(datetime.day))
+			.ok_or(CmosError::InvalidField(RtcField::Year))?;
+		// The fields were validated above, so only the date can be rejected
+		date.and_hms_opt(u32::from(datetime.hour), u32::from(datetime.minute), u32::from(datetime.second))
+			.ok_or(CmosError::InvalidField(RtcField::Hour))
+	}
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::NaiveDateTime> for RTCDateTime {
+	type Error = CmosError;
+
+	/// Drops the fractional seconds, and fails with `CmosError::InvalidField(RtcField::Year)` for years before 0
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{CmosError, RTCDateTime, RtcField};
+	/// # use core::convert::TryFrom;
+	/// use chrono::NaiveDate;
+	/// let naive = NaiveDate::from_ymd_opt(2020, 2, 29).unwrap().and_hms_milli_opt(23, 59, 59, 999).unwrap();
+	/// assert_eq!(RTCDateTime::try_from(naive).unwrap().as_tuple(), (2020, 2, 29, 23, 59, 59));
+	///
+	/// let bc = NaiveDate::from_ymd_opt(-1, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+	/// assert_eq!(RTCDateTime::try_from(bc), Err(CmosError::InvalidField(RtcField::Year)));
+	/// ```
+	fn try_from(naive: chrono::NaiveDateTime) -> Result<RTCDateTime, CmosError> {
+		use chrono::{Datelike, Timelike};
+		Ok(RTCDateTime {
+			year: usize::try_from(naive.year()).map_err(|_| CmosError::InvalidField(RtcField::Year))?,
+			month: naive.month() as u8,
+			day: naive.day() as u8,
+			hour: naive.hour() as u8,
+			minute: naive.minute() as u8,
+			second: naive.second() as u8,
+		})
+	}
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<RTCDateTime> for time::PrimitiveDateTime {
+	type Error = CmosError;
+
+	/// Fails with `CmosError::InvalidField` and the first field out of range, or with `RtcField::Year` if the year
+	/// is beyond the time crate's range
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{CmosError, RTCDateTime, RtcField};
+	/// # use core::convert::TryFrom;
+	/// use time::PrimitiveDateTime;
+	/// let datetime = RTCDateTime { year: 2019, month: 1, day: 2, hour: 3, minute: 4, second: 5 };
+	/// let primitive = PrimitiveDateTime::try_from(datetime).unwrap();
+	/// assert_eq!((primitive.year(), u8::from(primitive.month()), primitive.day()), (2019, 1, 2));
+	/// assert_eq!(primitive.as_hms(), (3, 4, 5));
+	/// assert_eq!(RTCDateTime::try_from(primitive), Ok(datetime));
+	///
+	/// let feb_30 = RTCDateTime { month: 2, day: 30, ..datetime };
+	/// assert_eq!(PrimitiveDateTime::try_from(feb_30), Err(CmosError::InvalidField(RtcField::Day)));
+	/// let far = RTCDateTime { year: 1_000_000, ..datetime };
+	/// assert_eq!(PrimitiveDateTime::try_from(far), Err(CmosError::InvalidField(RtcField::Year)));
+	/// ```
+	fn try_from(datetime: RTCDateTime) -> Result<time::PrimitiveDateTime, CmosError> {
+		if let Some(field) = datetime.invalid_field() {
+			return Err(CmosError::InvalidField(field));
+		}
+		let year = i32::try_from(datetime.year).map_err(|_| CmosError::InvalidField(RtcField::Year))?;
+		let month = time::Month::try_from(datetime.month).map_err(|_| CmosError::InvalidField(RtcField::Month))?;
+		let date = time::Date::from_calendar_date(year, month, datetime.day)
+			.map_err(|_| CmosError::InvalidField(RtcField::Year))?;
+		// The fields were validated above, so only the date can be rejected
+		let clock = time::Time::from_hms(datetime.hour, datetime.minute, datetime.second)
+			.map_err(|_| CmosError::InvalidField(RtcField::Hour))?;
+		Ok(time::PrimitiveDateTime::new(date, clock))
+	}
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<time::PrimitiveDateTime> for RTCDateTime {
+	type Error = CmosError;
+
+	/// Drops the fractional seconds, and fails with `CmosError::InvalidField(RtcField::Year)` for years before 0
+	///
+	/// # Examples
+	/// ```rust
+	/// # use cmos::{CmosError, RTCDateTime, RtcField};
+	/// # use core::convert::TryFrom;
+	/// use time::{Date, Month, PrimitiveDateTime, Time};
+	/// let date = Date::from_calendar_date(2020, Month::February, 29).unwrap();
+	/// let primitive = PrimitiveDateTime::new(date, Time::from_hms_milli(23, 59, 59, 999).unwrap());
+	/// assert_eq!(RTCDateTime::try_from(primitive).unwrap().as_tuple(), (2020, 2, 29, 23, 59, 59));
+	///
+	/// let bc = PrimitiveDateTime::new(Date::from_calendar_date(-1, Month::January, 1).unwrap(), Time::MIDNIGHT);
+	/// assert_eq!(RTCDateTime::try_from(bc), Err(CmosError::InvalidField(RtcField::Year)));
+	/// ```
+	fn try_from(primitive: time::PrimitiveDateTime) -> Result<RTCDateTime, CmosError> {
+		Ok(RTCDateTime {
+			year: usize::try_from(primitive.year()).map_err(|_| CmosError::InvalidField(RtcField::Year))?,
+			month: u8::from(primitive.month()),
+			day: primitive.day(),
+			hour: primitive.hour(),
+			minute: primitive.minute(),
+			second: primitive.second(),
+		})
+	}
+}